@@ -1,11 +1,15 @@
+pub mod cli;
 mod data;
+pub mod history;
+pub mod i18n;
+mod mouse;
+mod sheet;
+pub mod simulate;
+pub mod theme;
 mod widgets;
 
-use std::borrow::BorrowMut;
-
 use crossterm::event::KeyCode;
 
-use rand::Rng;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
@@ -14,16 +18,27 @@ use tui::{
 
 use self::{
     data::{
-        capture::{capture_position, Capture, CAPTURE_POINTS, CENTER_CAPTURE_MULTIPLIER},
+        board::{Board, Feature, CODE_LEN, FEATURES},
+        capture::Location,
+        challenge::{Challenge, ChallengeOutcome, Deck, Vote},
+        database::DatabaseError,
         game::{
-            AiSide, Game, Position, CHALLENGE_POINTS, DATABASE_POINTS, MAX_PLAYERS, MIN_PLAYERS,
-            RACE_PROB,
+            AiSide, Game, Outcome, PlacementError, Position, MAX_PLAYERS, MAX_POSITION_LEN,
+            MIN_PLAYERS,
         },
-        race::{Race, RACE_POINTS},
+        handicap::{Handicap, HANDICAP_FIELDS},
+        history::{Filter, History, Record},
+        ratings::{Ratings, MAX_PLAYER_NAME},
+        score::Source,
         side::Side,
-        vpn::vpn_position,
+        timeline::Moment,
+        tournament::{MatchResult, Registration, Tournament, MAX_TEAM_NAME, MIN_TEAMS},
+        venue::VENUE_FIELDS,
     },
-    widgets::title::Title,
+    i18n::tr,
+    mouse::Targets,
+    sheet::{rule_lines, Sheet},
+    widgets::{timeline::MIN_TIMELINE_HEIGHT, title::Title},
 };
 
 pub trait Drawable {
@@ -33,33 +48,130 @@ pub trait Drawable {
 #[derive(Clone)]
 pub enum AppState {
     PlayerInput(AiSide),
+    RosterInput,
+    ModeInput,
+    VenueInput,
+    HandicapInput,
     VPNPositions,
     CapturePositions,
     Play,
-    Finished(AiSide),
-    Challenge(&'static str),
+    // The referee enters where the team placed its database
+    DatabaseInput(AiSide),
+    Finished(Option<AiSide>),
+    TournamentSetup,
+    Standings,
+    Leaderboard,
+    History,
+    Challenge(Challenge),
+    ChallengeHistory,
+}
+
+impl AppState {
+    // Setup step reached with Shift+Tab
+    fn previous_step(&self) -> Option<AppState> {
+        Some(match self {
+            AppState::PlayerInput(AiSide::Against) => AppState::PlayerInput(AiSide::For),
+            AppState::RosterInput => AppState::PlayerInput(AiSide::Against),
+            AppState::ModeInput => AppState::RosterInput,
+            AppState::VenueInput => AppState::ModeInput,
+            AppState::HandicapInput => AppState::VenueInput,
+            AppState::VPNPositions => AppState::HandicapInput,
+            AppState::CapturePositions => AppState::VPNPositions,
+            _ => return None,
+        })
+    }
 }
 
 pub struct App<'a> {
     title: Title<'a>,
     game: Game,
     state: AppState,
-    vpn_positions: Option<(Position, Position)>,
-    capture_positions: Option<(Position, Position, Position)>,
+    // VPNs and capture points, generated once the handicaps are set
+    board: Option<Board>,
+    handicap_field: usize,
+    venue_field: usize,
+    // Item picked on the VPN and capture steps, typed in input when entering_position
+    position_field: usize,
+    entering_position: bool,
+    // Setup code typed in input, giving back a noted layout
+    entering_code: bool,
+    position_error: Option<PlacementError>,
+    // Outcome of the last setup sheet export
+    sheet_status: Option<Result<(), String>>,
+    input: String,
+    database_error: Option<DatabaseError>,
+    registration: Registration,
+    tournament: Option<Tournament>,
+    tournament_match: Option<usize>,
+    tournament_error: Option<String>,
+    // The file could not be read, it is kept as is instead of being overwritten
+    tournament_unreadable: bool,
+    roster_slot: usize,
+    ratings: Ratings,
+    ratings_error: Option<String>,
+    // The file could not be read, it is kept as is instead of being overwritten
+    ratings_unreadable: bool,
+    history: History,
+    history_error: Option<String>,
+    // The file could not be read, it is kept as is instead of being overwritten
+    history_unreadable: bool,
+    history_filter: Filter,
+    // Name filter typed in input
+    entering_filter: bool,
+    // Details of the selected game instead of the stats
+    history_details: bool,
+    // Delete pressed once on the selected game
+    confirm_delete: bool,
+    scroll: u16,
+    targets: Targets,
+    deck: Deck,
     pub should_quit: bool,
 }
 
 impl App<'_> {
     pub fn new() -> App<'static> {
+        let (ratings, ratings_error) = match Ratings::load() {
+            Ok(ratings) => (ratings, None),
+            Err(err) => (Ratings::empty(), Some(tr!("leaderboard.unreadable", err))),
+        };
+        let (history, history_error) = match History::load() {
+            Ok(history) => (history, None),
+            Err(err) => (History::empty(), Some(tr!("history.unreadable", err))),
+        };
         App {
-            title: Title::new(
-                "Welcome to CyberConnect!    Fight against the other team to control the AI \"Lucy\".    You have many funny mechanics to discover along the way :)    Have Fun !",
-            ),
+            title: Title::new(tr!("title.welcome")),
             game: Game::new(),
             state: AppState::PlayerInput(AiSide::For),
             should_quit: false,
-            vpn_positions: None,
-            capture_positions: None,
+            board: None,
+            handicap_field: 0,
+            venue_field: 0,
+            position_field: 0,
+            entering_position: false,
+            entering_code: false,
+            position_error: None,
+            sheet_status: None,
+            input: String::new(),
+            database_error: None,
+            registration: Registration::new(),
+            tournament: None,
+            tournament_match: None,
+            tournament_error: None,
+            tournament_unreadable: false,
+            roster_slot: 0,
+            ratings_unreadable: ratings_error.is_some(),
+            ratings,
+            ratings_error,
+            history_unreadable: history_error.is_some(),
+            history,
+            history_error,
+            history_filter: Filter::default(),
+            entering_filter: false,
+            history_details: false,
+            confirm_delete: false,
+            scroll: 0,
+            targets: Targets::default(),
+            deck: Deck::default(),
         }
     }
 }
@@ -71,16 +183,22 @@ impl App<'_> {
             .constraints([Constraint::Length(2), Constraint::Max(80)].as_ref())
             .split(f.size());
 
+        self.targets.clear();
         self.title.draw(self, f, chunks[0]);
 
         match self.state.clone() {
             AppState::PlayerInput(ai_side) => {
                 self.draw_setup(f, chunks[1], ai_side);
             }
-            AppState::VPNPositions | AppState::CapturePositions => {
+            AppState::RosterInput
+            | AppState::ModeInput
+            | AppState::VenueInput
+            | AppState::HandicapInput
+            | AppState::VPNPositions
+            | AppState::CapturePositions => {
                 self.draw_setup(f, chunks[1], AiSide::For);
             }
-            AppState::Play => {
+            AppState::Play | AppState::DatabaseInput(_) => {
                 let chunks = Layout::default()
                     .direction(Direction::Vertical)
                     .margin(10)
@@ -89,11 +207,12 @@ impl App<'_> {
                             Constraint::Length(9), // Progress
                             Constraint::Length(1),
                             Constraint::Length(4), // Captures
-                            Constraint::Length(1),
-                            Constraint::Length(3), // Race
+                            Constraint::Length(2), // VPNs
+                            // Running races and the recent ones
+                            Constraint::Length(self.game.rules.max_races as u16 + 3),
                             Constraint::Length(1),
                             Constraint::Length(9), // Keys
-                            Constraint::Min(0),
+                            Constraint::Min(0),    // Progress over time, when there is room
                         ]
                         .as_ref(),
                     )
@@ -116,44 +235,60 @@ impl App<'_> {
                 self.game.draw_side(f, progress_chunks[0], AiSide::For);
                 self.game.draw_progress(f, progress_chunks[2]);
                 self.game.draw_side(f, progress_chunks[4], AiSide::Against);
-                self.game.draw_captures(f, chunks[2]);
-                self.game.draw_race(f, chunks[4]);
-                self.game.draw_keys(f, chunks[6]);
+                self.game.draw_event(f, chunks[1]);
+                self.game.draw_captures(f, chunks[2], &mut self.targets);
+                self.game.draw_vpns(f, chunks[3], &mut self.targets);
+                self.game.draw_race(f, chunks[4], &mut self.targets);
+                if let AppState::DatabaseInput(ai_side) = &self.state {
+                    self.draw_database_input(f, chunks[6], ai_side.clone());
+                } else {
+                    self.game.draw_keys(f, chunks[6], &mut self.targets);
+                }
+                if chunks[7].height >= MIN_TIMELINE_HEIGHT {
+                    self.game.draw_timeline(f, chunks[7]);
+                }
             }
-            AppState::Challenge(text) => {
-                self.draw_challenge(f, chunks[1], text);
+            AppState::Challenge(challenge) => {
+                self.draw_challenge(f, chunks[1], &challenge);
             }
             AppState::Finished(ai_side) => {
                 self.draw_finished(f, chunks[1], ai_side);
             }
+            AppState::TournamentSetup => {
+                self.draw_registration(f, chunks[1]);
+            }
+            AppState::Standings => {
+                self.draw_standings(f, chunks[1]);
+            }
+            AppState::Leaderboard => {
+                self.draw_leaderboard(f, chunks[1]);
+            }
+            AppState::History => {
+                self.draw_history(f, chunks[1]);
+            }
+            AppState::ChallengeHistory => {
+                self.draw_challenge_history(f, chunks[1]);
+            }
         }
     }
 }
 
 impl App<'_> {
     pub fn on_tick(&mut self) {
-        if let Some(race) = &self.game.race {
-            if race.is_finished() {
-                self.game.race = None;
-            }
-        }
-
-        if let Some(ref mut center) = self.game.center_capture {
-            if center.count() == 0 {
-                self.game.center_capture = None;
-            }
+        if self.game.finished.is_none() {
+            self.game.expire_races();
         }
 
         // Winning condition
-        if let Some(ref mut for_ai) = self.game.for_ai {
-            if for_ai.has_won() {
-                self.state = AppState::Finished(AiSide::For)
-            }
-        }
-
-        if let Some(ref mut against_ai) = self.game.against_ai {
-            if against_ai.has_won() {
-                self.state = AppState::Finished(AiSide::Against)
+        if let AppState::Play = self.state {
+            match self.game.outcome() {
+                Some(Outcome::Won(ai_side)) => self.finish(Some(ai_side)),
+                Some(Outcome::Level) if self.game.rules.sudden_death => {
+                    self.game.sudden_death = true;
+                    self.state = AppState::Challenge(self.deck.draw(&self.game));
+                }
+                Some(Outcome::Level) => self.finish(None),
+                None => {}
             }
         }
     }
@@ -164,200 +299,786 @@ impl App<'_> {
             return;
         };
 
-        let mut rng = rand::thread_rng();
+        if let KeyCode::F(2) = code {
+            theme::next_theme();
+            return;
+        }
+
+        if let KeyCode::BackTab = code {
+            if let Some(previous) = self.state.previous_step() {
+                self.position_field = 0;
+                self.entering_position = false;
+                self.entering_code = false;
+                self.position_error = None;
+                self.sheet_status = None;
+                self.state = previous;
+                return;
+            }
+        }
+
         match &self.state {
+            AppState::PlayerInput(AiSide::For) if code == KeyCode::Char('t') => {
+                let loaded = Tournament::load();
+                self.tournament_unreadable = loaded.is_err();
+                match loaded {
+                    Ok(Some(tournament)) => {
+                        self.tournament = Some(tournament);
+                        self.scroll = 0;
+                        self.state = AppState::Standings;
+                    }
+                    Ok(None) => self.state = AppState::TournamentSetup,
+                    Err(err) => {
+                        self.tournament_error = Some(tr!("tournament.load_error", err));
+                        self.state = AppState::TournamentSetup;
+                    }
+                }
+            }
+            AppState::PlayerInput(AiSide::For) if code == KeyCode::Char('l') => {
+                self.scroll = 0;
+                self.state = AppState::Leaderboard;
+            }
+            AppState::PlayerInput(AiSide::For) if code == KeyCode::Char('h') => {
+                self.scroll = 0;
+                self.history_details = false;
+                self.confirm_delete = false;
+                self.state = AppState::History;
+            }
             AppState::PlayerInput(ai_side) => match code {
                 KeyCode::Enter => match ai_side {
                     AiSide::For => {
-                        if let Some(_) = self.game.for_ai {
+                        if self.game.for_ai.is_some() {
                             self.state = AppState::PlayerInput(AiSide::Against)
                         }
                     }
                     AiSide::Against => {
-                        if let Some(_) = self.game.against_ai {
-                            let mut rng = rand::thread_rng();
-                            self.vpn_positions = Some((
-                                vpn_position(rng.borrow_mut(), AiSide::For),
-                                vpn_position(rng.borrow_mut(), AiSide::Against),
-                            ));
-                            self.state = AppState::VPNPositions
+                        if self.game.against_ai.is_some() {
+                            self.roster_slot = 0;
+                            self.state = AppState::RosterInput
                         }
                     }
                 },
                 KeyCode::Char(c) => {
                     if let Some(d) = c.to_digit(10) {
                         let d = d as u8;
-                        if MIN_PLAYERS <= d && d <= MAX_PLAYERS {
-                            match ai_side {
-                                AiSide::For => {
-                                    self.game.for_ai = Some(Side::new(d as u8, AiSide::For))
-                                }
-                                AiSide::Against => {
-                                    self.game.against_ai = Some(Side::new(d as u8, AiSide::Against))
-                                }
-                            }
+                        let side = match ai_side {
+                            AiSide::For => &mut self.game.for_ai,
+                            AiSide::Against => &mut self.game.against_ai,
+                        };
+                        // Coming back to this step keeps the names unless the count changes
+                        if (MIN_PLAYERS..=MAX_PLAYERS).contains(&d)
+                            && side.as_ref().is_none_or(|side| side.nb_players != d)
+                        {
+                            *side = Some(Side::new(d));
                         }
                     }
                 }
                 _ => {}
             },
-            AppState::VPNPositions => match code {
-                KeyCode::Enter => {
-                    self.capture_positions = Some((
-                        capture_position(rng.borrow_mut(), AiSide::For),
-                        capture_position(rng.borrow_mut(), AiSide::Against),
-                        capture_position(rng.clone().borrow_mut(), rng.gen()),
-                    ));
-                    self.state = AppState::CapturePositions;
+            AppState::RosterInput => match code {
+                KeyCode::Up => self.roster_slot = self.roster_slot.saturating_sub(1),
+                KeyCode::Down => {
+                    let slots = self.game.for_ai.as_ref().map_or(0, |s| s.players.len())
+                        + self.game.against_ai.as_ref().map_or(0, |s| s.players.len());
+                    self.roster_slot = (self.roster_slot + 1).min(slots.saturating_sub(1));
+                }
+                KeyCode::Char(c) => {
+                    if let Some(name) = self.roster_name_mut() {
+                        if name.chars().count() < MAX_PLAYER_NAME && !(name.is_empty() && c == ' ')
+                        {
+                            name.push(c);
+                        }
+                    }
+                }
+                KeyCode::Backspace => {
+                    if let Some(name) = self.roster_name_mut() {
+                        name.pop();
+                    }
+                }
+                KeyCode::Tab => {
+                    if let (Some(for_ai), Some(against_ai)) =
+                        (self.game.for_ai.as_mut(), self.game.against_ai.as_mut())
+                    {
+                        if let (Some(for_names), Some(against_names)) =
+                            (for_ai.roster(), against_ai.roster())
+                        {
+                            let players = [for_names, against_names].concat();
+                            (for_ai.players, against_ai.players) =
+                                self.ratings.balance(&players, for_ai.players.len());
+                        }
+                    }
                 }
+                KeyCode::Enter => self.state = AppState::ModeInput,
                 _ => {}
             },
-            AppState::CapturePositions => match code {
-                KeyCode::Enter => self.state = AppState::Play,
+            AppState::ModeInput => match code {
+                KeyCode::Left => self.game.rules.mode = self.game.rules.mode.previous(),
+                KeyCode::Right => self.game.rules.mode = self.game.rules.mode.next(),
+                KeyCode::Up => self.game.rules.mode.increase(),
+                KeyCode::Down => self.game.rules.mode.decrease(),
+                KeyCode::Char('x') => self.game.rules.sudden_death = !self.game.rules.sudden_death,
+                KeyCode::Char('j') => self.game.rules.tie_rule = self.game.rules.tie_rule.next(),
+                KeyCode::Char('r') => {
+                    self.game.rules.race_balance = self.game.rules.race_balance.next()
+                }
+                KeyCode::Char('n') => self.game.rules.next_max_races(),
+                KeyCode::Char('l') => {
+                    self.game.rules.event_rate = self.game.rules.event_rate.next()
+                }
+                KeyCode::Char('c') => {
+                    self.game.rules.capture_transfer = self.game.rules.capture_transfer.next()
+                }
+                KeyCode::Char('d') => self.game.rules.next_database_supply(),
+                KeyCode::Char('s') => self.game.rules.raid = self.game.rules.raid.next(),
+                KeyCode::Enter => {
+                    self.venue_field = 0;
+                    self.state = AppState::VenueInput
+                }
                 _ => {}
             },
-            AppState::Play => match code {
-                KeyCode::Tab => {
-                    // Change turn (SWITCH TEAM)
-                    if let Some(ref mut side) = self.game.get_turn() {
-                        side.nb_rounds += 1;
+            AppState::VenueInput => match code {
+                KeyCode::Left => self.venue_field = self.venue_field.saturating_sub(1),
+                KeyCode::Right => self.venue_field = (self.venue_field + 1).min(VENUE_FIELDS - 1),
+                KeyCode::Char(' ') => self.game.rules.venue.toggle(self.venue_field),
+                // Handicaps set by hand are kept when coming back to this step
+                KeyCode::Enter => {
+                    let untouched = [&self.game.for_ai, &self.game.against_ai]
+                        .into_iter()
+                        .flatten()
+                        .all(|side| side.handicap == Handicap::none());
+                    if untouched {
+                        self.game.suggest_handicaps();
                     }
-                    self.game.turn = self.game.turn.switch();
-
-                    // Count capture points
-                    let mut center_captured = 0;
-                    if let Some(center) = &self.game.center_capture {
-                        if center.ai_side == self.game.turn {
-                            center_captured = center.count();
+                    self.handicap_field = 0;
+                    self.state = AppState::HandicapInput
+                }
+                _ => {}
+            },
+            AppState::HandicapInput => match code {
+                KeyCode::Up => self.handicap_field = self.handicap_field.saturating_sub(1),
+                KeyCode::Down => {
+                    self.handicap_field = (self.handicap_field + 1).min(HANDICAP_FIELDS.len() - 1)
+                }
+                KeyCode::Left | KeyCode::Right => {
+                    let (ai_side, field) = &HANDICAP_FIELDS[self.handicap_field];
+                    if let Some(side) = self.game.side_mut(ai_side) {
+                        if let KeyCode::Left = code {
+                            side.handicap.decrease(*field)
+                        } else {
+                            side.handicap.increase(*field)
                         }
                     }
-                    if let Some(ref mut side) = self.game.get_turn() {
-                        side.advance(
-                            CAPTURE_POINTS
-                                * (side.capture.count()
-                                    + (CENTER_CAPTURE_MULTIPLIER * center_captured as f32).round()
-                                        as u32),
-                        );
+                }
+                KeyCode::Char('g') => self.game.suggest_handicaps(),
+                KeyCode::Char('r') => {
+                    for side in [self.game.for_ai.as_mut(), self.game.against_ai.as_mut()]
+                        .into_iter()
+                        .flatten()
+                    {
+                        side.handicap = Handicap::none();
                     }
-
-                    if let None = self.game.race {
-                        if rng.gen_bool(RACE_PROB) {
-                            self.game.race = Some(Race::new());
+                }
+                // Positions already generated or entered are kept when coming back
+                KeyCode::Enter => {
+                    if self.board.is_none() {
+                        match Board::generate(&mut self.game.board_rng, &self.game.rules.board) {
+                            Ok(board) => self.board = Some(board),
+                            Err(err) => {
+                                self.position_error = Some(err);
+                                return;
+                            }
                         }
                     }
+                    self.position_error = None;
+                    self.position_field = 0;
+                    self.state = AppState::VPNPositions
                 }
-                KeyCode::Char(c) => {
-                    if let Some(ref mut for_ai) = self.game.for_ai {
-                        if let Some(ref mut against_ai) = self.game.against_ai {
-                            match c {
-                                '1' => {
-                                    if let Some(_) = self.game.race {
-                                        for_ai.advance(RACE_POINTS);
-                                        self.game.race = None;
-                                    }
-                                }
-                                '0' => {
-                                    if let Some(_) = self.game.race {
-                                        against_ai.advance(RACE_POINTS);
-                                        self.game.race = None;
-                                    }
-                                }
-
-                                '5' => for_ai.retreat(1),
-                                '7' => against_ai.retreat(1),
-
-                                'i' => for_ai.advance(DATABASE_POINTS),
-                                'd' => against_ai.advance(DATABASE_POINTS),
-
-                                'e' => for_ai.capture.add(),
-                                'u' => for_ai.capture.remove(),
-                                't' => against_ai.capture.add(),
-                                'h' => against_ai.capture.remove(),
-
-                                'a' => {
-                                    if let Some(ref mut center) = self.game.center_capture {
-                                        match center.ai_side {
-                                            AiSide::For => center.add(),
-                                            AiSide::Against => {
-                                                *center = Capture::new(AiSide::For);
-                                                center.add();
-                                            }
-                                        }
-                                    } else {
-                                        let mut center = Capture::new(AiSide::For);
-                                        center.add();
-                                        self.game.center_capture = Some(center);
-                                    }
-                                }
-                                'o' => {
-                                    if let Some(ref mut center) = self.game.center_capture {
-                                        if let AiSide::For = center.ai_side {
-                                            center.remove()
-                                        }
-                                    }
-                                }
-                                's' => {
-                                    if let Some(ref mut center) = self.game.center_capture {
-                                        match center.ai_side {
-                                            AiSide::Against => center.add(),
-                                            AiSide::For => {
-                                                *center = Capture::new(AiSide::Against);
-                                                center.add();
-                                            }
-                                        }
-                                    } else {
-                                        let mut center = Capture::new(AiSide::Against);
-                                        center.add();
-                                        self.game.center_capture = Some(center);
-                                    }
-                                }
-                                'n' => {
-                                    if let Some(ref mut center) = self.game.center_capture {
-                                        if let AiSide::Against = center.ai_side {
-                                            center.remove()
-                                        }
-                                    }
+                _ => {}
+            },
+            AppState::VPNPositions | AppState::CapturePositions if self.entering_code => match code
+            {
+                KeyCode::Backspace if self.input.is_empty() => self.entering_code = false,
+                KeyCode::Backspace => {
+                    self.input.pop();
+                }
+                KeyCode::Char(c) if c.is_ascii_alphanumeric() && self.input.len() < CODE_LEN => {
+                    self.input.push(c.to_ascii_uppercase())
+                }
+                KeyCode::Enter => match Board::from_code(&self.input) {
+                    Ok(board) => {
+                        self.board = Some(board);
+                        self.position_error = None;
+                        self.entering_code = false;
+                    }
+                    Err(err) => self.position_error = Some(err),
+                },
+                _ => {}
+            },
+            // Typed position of the picked item, checked against the board rules
+            AppState::VPNPositions | AppState::CapturePositions if self.entering_position => {
+                match code {
+                    KeyCode::Backspace if self.input.is_empty() => self.entering_position = false,
+                    KeyCode::Backspace => {
+                        self.input.pop();
+                    }
+                    KeyCode::Char(c)
+                        if c.is_ascii_alphanumeric() && self.input.len() < MAX_POSITION_LEN =>
+                    {
+                        self.input.push(c)
+                    }
+                    KeyCode::Enter => {
+                        let feature = self.step_feature();
+                        if let (Some(board), Some(feature)) = (self.board.as_mut(), feature) {
+                            let position =
+                                self.input.parse().map_err(PlacementError::from).and_then(
+                                    |position| board.check(feature, &position).map(|()| position),
+                                );
+                            match position {
+                                Ok(position) => {
+                                    board.set(feature, position);
+                                    self.position_error = None;
+                                    self.entering_position = false;
                                 }
+                                Err(err) => self.position_error = Some(err),
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            AppState::VPNPositions | AppState::CapturePositions
+                if matches!(
+                    code,
+                    KeyCode::Up
+                        | KeyCode::Down
+                        | KeyCode::Char('r')
+                        | KeyCode::Char('e')
+                        | KeyCode::Char('g')
+                        | KeyCode::Char('s')
+                        | KeyCode::Char('d')
+                        | KeyCode::Char('k')
+                        | KeyCode::Char('x')
+                ) =>
+            {
+                let last = self.step_features().len().saturating_sub(1);
+                let feature = self.step_feature();
+                let constraints = &mut self.game.rules.board;
+                self.position_error = None;
+                self.sheet_status = None;
+                match code {
+                    KeyCode::Up => self.position_field = self.position_field.saturating_sub(1),
+                    KeyCode::Down => self.position_field = (self.position_field + 1).min(last),
+                    KeyCode::Char('r') => {
+                        if let (Some(board), Some(feature)) = (self.board.as_mut(), feature) {
+                            self.position_error = board
+                                .reroll(&mut self.game.board_rng, feature, constraints)
+                                .err();
+                        }
+                    }
+                    KeyCode::Char('e') => {
+                        self.input.clear();
+                        self.entering_position = true;
+                    }
+                    KeyCode::Char('k') => {
+                        self.input.clear();
+                        self.entering_code = true;
+                    }
+                    KeyCode::Char('x') => {
+                        if let Some(board) = &self.board {
+                            self.sheet_status = Some(
+                                Sheet::new(&self.game, board)
+                                    .save()
+                                    .map_err(|err| err.to_string()),
+                            );
+                        }
+                    }
+                    // New constraints give a whole new board, the current one stays when they cannot be met
+                    _ => {
+                        match code {
+                            KeyCode::Char('s') => constraints.symmetric = !constraints.symmetric,
+                            KeyCode::Char('d') => constraints.next_spacing(),
+                            _ => {}
+                        }
+                        match Board::generate(&mut self.game.board_rng, constraints) {
+                            Ok(board) => self.board = Some(board),
+                            Err(err) => self.position_error = Some(err),
+                        }
+                    }
+                }
+            }
+            AppState::VPNPositions => match code {
+                KeyCode::Char('u') => self.game.rules.next_vpn_uses(),
+                KeyCode::Char('c') => self.game.rules.next_vpn_cooldown(),
+                KeyCode::Char('m') => self.game.rules.next_vpn_relocate(),
+                KeyCode::Enter => {
+                    self.position_field = 0;
+                    self.state = AppState::CapturePositions;
+                }
+                _ => {}
+            },
+            AppState::CapturePositions => {
+                if let KeyCode::Enter = code {
+                    if let Some(board) = &self.board {
+                        self.game.place_vpns(board);
+                        self.game.place_captures(board);
+                    }
+                    self.game.begin();
+                    self.state = AppState::Play
+                }
+            }
+            AppState::Play => match code {
+                KeyCode::Tab => self.game.end_turn(),
+                // One key per running race and team, the oldest race first
+                KeyCode::Char(c @ ('1' | '2' | '3')) => {
+                    self.game.claim_race(c as usize - '1' as usize, AiSide::For)
+                }
+                KeyCode::Char(c @ ('i' | 'd')) => {
+                    self.input.clear();
+                    self.database_error = None;
+                    self.state = AppState::DatabaseInput(match c {
+                        'i' => AiSide::For,
+                        _ => AiSide::Against,
+                    });
+                }
+                // Teleports through the team's VPN
+                KeyCode::Char('p') => self.game.use_vpn(AiSide::For),
+                KeyCode::Char('g') => self.game.use_vpn(AiSide::Against),
+                KeyCode::Char(c @ ('0' | '9' | '8')) => {
+                    let index = match c {
+                        '0' => 0,
+                        '9' => 1,
+                        _ => 2,
+                    };
+                    self.game.claim_race(index, AiSide::Against)
+                }
+                // Capture point occupants, shifted home keys move the other team
+                KeyCode::Char(
+                    c @ ('e' | 'u' | 'E' | 'U' | 't' | 'h' | 'T' | 'H' | 'a' | 'o' | 's' | 'n'),
+                ) => {
+                    let (location, ai_side) = match c {
+                        'e' | 'u' => (Location::Home(AiSide::For), AiSide::For),
+                        'E' | 'U' => (Location::Home(AiSide::For), AiSide::Against),
+                        't' | 'h' => (Location::Home(AiSide::Against), AiSide::Against),
+                        'T' | 'H' => (Location::Home(AiSide::Against), AiSide::For),
+                        'a' | 'o' => (Location::Center, AiSide::For),
+                        _ => (Location::Center, AiSide::Against),
+                    };
+                    if let 'e' | 'E' | 't' | 'T' | 'a' | 's' = c {
+                        self.game.add_capture(&location, ai_side);
+                    } else {
+                        self.game.remove_capture(&location, ai_side);
+                    }
+                }
+                // WHY ARE YOU EVEN READING THIS ? IT IS HORRIBLE...
+                KeyCode::Char(c)
+                    if self.game.for_ai.is_some() && self.game.against_ai.is_some() =>
+                {
+                    match c {
+                        '5' => self.game.score(&AiSide::For, Source::Penalties, -1),
+                        '7' => self.game.score(&AiSide::Against, Source::Penalties, -1),
 
-                                'f' => self.state = AppState::Challenge(self.game.pick_challenge()),
-                                _ => {}
-                            };
+                        'f' => self.state = AppState::Challenge(self.deck.draw(&self.game)),
+                        'v' => {
+                            self.scroll = 0;
+                            self.state = AppState::ChallengeHistory
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            },
+            AppState::DatabaseInput(ai_side) => match code {
+                // Arrows pick the position on the board, starting from the team's base
+                KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
+                    let (rows, cols) = match code {
+                        KeyCode::Up => (-1, 0),
+                        KeyCode::Down => (1, 0),
+                        KeyCode::Left => (0, -1),
+                        _ => (0, 1),
+                    };
+                    let position = match self.input.parse::<Position>() {
+                        Ok(position) => position.shifted(rows, cols).unwrap_or(position),
+                        Err(_) => ai_side.base(),
+                    };
+                    self.input = position.to_string();
+                    self.database_error = None;
+                }
+                KeyCode::Backspace if self.input.is_empty() => self.state = AppState::Play,
+                KeyCode::Backspace => {
+                    self.input.pop();
+                }
+                KeyCode::Char(c)
+                    if c.is_ascii_alphanumeric() && self.input.len() < MAX_POSITION_LEN =>
+                {
+                    self.input.push(c)
+                }
+                // An empty position places the database without recording where
+                KeyCode::Enter => {
+                    let position = match self.input.trim() {
+                        "" => Ok(None),
+                        input => input.parse().map(Some),
+                    };
+                    match position
+                        .map_err(DatabaseError::from)
+                        .and_then(|position| self.game.place_database(ai_side.clone(), position))
+                    {
+                        Ok(()) => {
+                            self.input.clear();
+                            self.state = AppState::Play
                         }
-                        // WHY ARE YOU EVEN READING THIS ? IT IS HORRIBLE...
+                        Err(err) => self.database_error = Some(err),
                     }
                 }
                 _ => {}
             },
-            AppState::Challenge(text) => {
+            // Each judge votes in turn, the challenge ends once every ballot is cast
+            AppState::Challenge(current)
+                if current
+                    .vote
+                    .as_ref()
+                    .is_some_and(|vote| !vote.is_complete()) =>
+            {
+                let mut challenge = current.clone();
+                let ballot = match code {
+                    KeyCode::Char('1') => Some(AiSide::For),
+                    KeyCode::Char('0') => Some(AiSide::Against),
+                    KeyCode::Char('a') => None,
+                    KeyCode::Char('q') => {
+                        challenge.vote = None;
+                        self.state = AppState::Challenge(challenge);
+                        return;
+                    }
+                    _ => return,
+                };
+                if let Some(vote) = challenge.vote.as_mut() {
+                    vote.cast(ballot);
+                }
+                self.tally(challenge);
+            }
+            AppState::Challenge(current) => {
                 match code {
+                    KeyCode::Char('j') if current.judged && self.game.judges() > 0 => {
+                        let mut challenge = current.clone();
+                        challenge.vote = Some(Vote::new(self.game.judges()));
+                        self.state = AppState::Challenge(challenge);
+                    }
+                    // Pick a different challenge, the deck never draws the same one twice in a row
                     KeyCode::Char('f') => {
-                        // Pick a different challenge
-                        self.state = AppState::Challenge(loop {
-                            let challenge = self.game.pick_challenge();
-                            if challenge != *text {
-                                break challenge;
-                            }
-                        })
+                        self.state = AppState::Challenge(self.deck.draw(&self.game))
+                    }
+                    KeyCode::Char('b') => {
+                        self.deck.ban(current.key);
+                        self.state = AppState::Challenge(self.deck.draw(&self.game))
+                    }
+                    // Same challenge, new fill-ins
+                    KeyCode::Char('F') => {
+                        let mut challenge = current.clone();
+                        challenge.reroll();
+                        self.state = AppState::Challenge(challenge);
                     }
 
-                    KeyCode::Char('q') => self.state = AppState::Play,
+                    KeyCode::Char('q') if !self.game.sudden_death => {
+                        self.deck.record(current, None, &self.game);
+                        self.state = AppState::Play
+                    }
 
-                    KeyCode::Char('1') => {
-                        if let Some(ref mut for_ai) = self.game.for_ai {
-                            for_ai.advance(CHALLENGE_POINTS)
+                    KeyCode::Char('t')
+                    | KeyCode::Left
+                    | KeyCode::Right
+                    | KeyCode::Up
+                    | KeyCode::Down => {
+                        let mut challenge = current.clone();
+                        match code {
+                            KeyCode::Char('t') => challenge.toggle_timer(),
+                            KeyCode::Left => challenge.decrease_duration(),
+                            KeyCode::Right => challenge.increase_duration(),
+                            KeyCode::Up => challenge.increase_points(),
+                            _ => challenge.decrease_points(),
                         }
-                        self.state = AppState::Play
+                        self.state = AppState::Challenge(challenge);
+                    }
+
+                    KeyCode::Char('1') => {
+                        self.end_challenge(current.clone(), ChallengeOutcome::Won(AiSide::For))
                     }
                     KeyCode::Char('0') => {
-                        if let Some(ref mut against_ai) = self.game.against_ai {
-                            against_ai.advance(CHALLENGE_POINTS)
+                        self.end_challenge(current.clone(), ChallengeOutcome::Won(AiSide::Against))
+                    }
+                    KeyCode::Char('d') => {
+                        self.end_challenge(current.clone(), ChallengeOutcome::Draw)
+                    }
+                    KeyCode::Char('x') => {
+                        self.end_challenge(current.clone(), ChallengeOutcome::BothFailed)
+                    }
+                    _ => {}
+                }
+            }
+            AppState::Finished(ai_side) => {
+                if let (KeyCode::Enter, Some(index)) = (code, self.tournament_match) {
+                    if let (Some(tournament), Some(for_ai), Some(against_ai)) = (
+                        &mut self.tournament,
+                        &self.game.for_ai,
+                        &self.game.against_ai,
+                    ) {
+                        tournament.record(
+                            index,
+                            MatchResult {
+                                home_progress: for_ai.progress(),
+                                away_progress: against_ai.progress(),
+                                winner: ai_side.clone(),
+                            },
+                        );
+                    }
+                    self.tournament_match = None;
+                    self.save_tournament();
+                    self.scroll = 0;
+                    self.state = AppState::Standings;
+                } else if code == KeyCode::Char('a') && self.tournament_match.is_none() {
+                    self.play_again();
+                }
+            }
+            AppState::TournamentSetup => match code {
+                KeyCode::Tab => self.registration.format = self.registration.format.switch(),
+                KeyCode::Backspace if self.input.is_empty() => {
+                    self.registration.teams.pop();
+                }
+                KeyCode::Backspace => {
+                    self.input.pop();
+                }
+                KeyCode::Char(c) if self.input.chars().count() < MAX_TEAM_NAME => {
+                    self.input.push(c)
+                }
+                KeyCode::Enter => {
+                    if !self.input.trim().is_empty() {
+                        self.registration.add(&self.input);
+                        self.input.clear();
+                    } else if self.registration.teams.len() >= MIN_TEAMS {
+                        let registration =
+                            std::mem::replace(&mut self.registration, Registration::new());
+                        self.tournament =
+                            Some(Tournament::new(registration.format, registration.teams));
+                        self.save_tournament();
+                        self.scroll = 0;
+                        self.state = AppState::Standings;
+                    }
+                }
+                _ => {}
+            },
+            AppState::ChallengeHistory => match code {
+                KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+                KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+                KeyCode::Enter | KeyCode::Backspace | KeyCode::Char('q') | KeyCode::Char('v') => {
+                    self.state = AppState::Play
+                }
+                _ => {}
+            },
+            AppState::History if self.entering_filter => match code {
+                KeyCode::Char(c) if self.input.chars().count() < MAX_PLAYER_NAME => {
+                    self.input.push(c);
+                    self.history_filter.name = self.input.clone();
+                    self.scroll = 0;
+                }
+                KeyCode::Backspace => {
+                    self.input.pop();
+                    self.history_filter.name = self.input.clone();
+                    self.scroll = 0;
+                }
+                KeyCode::Enter => self.entering_filter = false,
+                _ => {}
+            },
+            AppState::History => {
+                let games = self.history.filtered(&self.history_filter);
+                let selected = games.get(self.scroll as usize).map(|record| record.id);
+                if code != KeyCode::Delete {
+                    self.confirm_delete = false;
+                }
+                match code {
+                    KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+                    KeyCode::Down => {
+                        self.scroll = (self.scroll + 1).min(games.len().saturating_sub(1) as u16)
+                    }
+                    KeyCode::Char('/') => {
+                        self.input = self.history_filter.name.clone();
+                        self.entering_filter = true;
+                    }
+                    KeyCode::Tab => {
+                        self.history_filter.next_outcome();
+                        self.scroll = 0;
+                    }
+                    KeyCode::Enter => self.history_details = !self.history_details,
+                    KeyCode::Delete => match selected {
+                        Some(id) if self.confirm_delete => {
+                            self.history.remove(id);
+                            self.save_history();
+                            self.confirm_delete = false;
+                            self.scroll = self.scroll.saturating_sub(1);
                         }
-                        self.state = AppState::Play
+                        Some(_) => self.confirm_delete = true,
+                        None => {}
+                    },
+                    KeyCode::Backspace | KeyCode::Char('q') => {
+                        self.state = AppState::PlayerInput(AiSide::For)
                     }
                     _ => {}
                 }
             }
-            AppState::Finished(_) => {}
+            AppState::Leaderboard => match code {
+                KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+                KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+                KeyCode::Enter | KeyCode::Backspace | KeyCode::Char('q') => {
+                    self.state = AppState::PlayerInput(AiSide::For)
+                }
+                _ => {}
+            },
+            AppState::Standings => match code {
+                KeyCode::Enter => {
+                    if let Some(tournament) = &mut self.tournament {
+                        if let Some(index) = tournament.next_match() {
+                            let m = &tournament.matches[index];
+                            self.game = Game::new();
+                            self.game.team_names = Some((
+                                tournament.teams[m.home].clone(),
+                                tournament.teams[m.away].clone(),
+                            ));
+                            self.board = None;
+                            self.tournament_match = Some(index);
+                            self.state = AppState::PlayerInput(AiSide::For);
+                        }
+                    }
+                }
+                KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+                KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+                KeyCode::Char('n') => {
+                    self.tournament = None;
+                    if !self.tournament_unreadable {
+                        self.tournament_error = None;
+                    }
+                    self.state = AppState::TournamentSetup;
+                }
+                _ => {}
+            },
+        }
+    }
+}
+
+impl App<'_> {
+    // Features listed on the current setup step
+    fn step_features(&self) -> &'static [Feature] {
+        match self.state {
+            AppState::VPNPositions => &FEATURES[..2],
+            AppState::CapturePositions => &FEATURES[2..],
+            _ => &[],
         }
     }
+
+    fn step_feature(&self) -> Option<&'static Feature> {
+        self.step_features().get(self.position_field)
+    }
+
+    fn save_tournament(&mut self) {
+        if let (Some(tournament), false) = (&self.tournament, self.tournament_unreadable) {
+            self.tournament_error = tournament
+                .save()
+                .err()
+                .map(|err| tr!("tournament.save_error", err));
+        }
+    }
+}
+
+impl App<'_> {
+    fn roster_name_mut(&mut self) -> Option<&mut String> {
+        let for_ai = self.game.for_ai.as_mut()?;
+        if self.roster_slot < for_ai.players.len() {
+            return for_ai.players.get_mut(self.roster_slot);
+        }
+        let slot = self.roster_slot - for_ai.players.len();
+        self.game.against_ai.as_mut()?.players.get_mut(slot)
+    }
+
+    // Back to the rules step with the same teams, a new board is generated
+    fn play_again(&mut self) {
+        let mut game = Game::new();
+        game.for_ai = self.game.for_ai.as_ref().map(Side::rematch);
+        game.against_ai = self.game.against_ai.as_ref().map(Side::rematch);
+        std::mem::swap(&mut game.rules, &mut self.game.rules);
+        self.game = game;
+        self.board = None;
+        self.position_field = 0;
+        self.position_error = None;
+        self.sheet_status = None;
+        self.state = AppState::ModeInput;
+    }
+
+    fn finish(&mut self, winner: Option<AiSide>) {
+        self.game.finish();
+        if let (Some(for_ai), Some(against_ai)) = (&self.game.for_ai, &self.game.against_ai) {
+            if let (Some(for_names), Some(against_names)) = (for_ai.roster(), against_ai.roster()) {
+                self.ratings
+                    .update(&for_names, &against_names, winner.clone());
+                if !self.ratings_unreadable {
+                    self.ratings_error = self
+                        .ratings
+                        .save()
+                        .err()
+                        .map(|err| tr!("leaderboard.error", err));
+                }
+            }
+        }
+        let record = Record::new(
+            &self.game,
+            winner.clone(),
+            self.board.as_ref().map(Board::code),
+            rule_lines(&self.game.rules),
+        );
+        self.history.add(record);
+        self.save_history();
+        self.state = AppState::Finished(winner);
+    }
+
+    fn save_history(&mut self) {
+        if !self.history_unreadable {
+            self.history_error = self
+                .history
+                .save()
+                .err()
+                .map(|err| tr!("history.error", err));
+        }
+    }
+
+    // Ends the challenge once the vote is over, unless the referee has to break the tie
+    fn tally(&mut self, challenge: Challenge) {
+        let verdict = challenge
+            .vote
+            .as_ref()
+            .filter(|vote| vote.is_complete())
+            .and_then(|vote| vote.verdict(self.game.rules.tie_rule, &self.game));
+        match verdict {
+            Some(outcome) => self.end_challenge(challenge, outcome),
+            None => self.state = AppState::Challenge(challenge),
+        }
+    }
+
+    fn end_challenge(&mut self, challenge: Challenge, outcome: ChallengeOutcome) {
+        self.deck
+            .record(&challenge, Some(outcome.clone()), &self.game);
+        self.game.mark(Moment::Challenge(match &outcome {
+            ChallengeOutcome::Won(ai_side) => Some(ai_side.clone()),
+            _ => None,
+        }));
+
+        // Sudden death, the challenge decides the winner or another one is played
+        if self.game.sudden_death {
+            match outcome {
+                ChallengeOutcome::Won(ai_side) => self.finish(Some(ai_side)),
+                _ => self.state = AppState::Challenge(self.deck.draw(&self.game)),
+            }
+            return;
+        }
+
+        let (for_points, against_points) = challenge.rewards(&outcome);
+        self.game
+            .score(&AiSide::For, Source::Challenges, for_points as i64);
+        self.game
+            .score(&AiSide::Against, Source::Challenges, against_points as i64);
+        self.state = AppState::Play
+    }
 }
@@ -0,0 +1,389 @@
+use std::time::Duration;
+
+use super::{
+    data::{
+        board::Board,
+        challenge::Category,
+        game::{AiSide, Game, MAX_PLAYERS, MIN_PLAYERS},
+        rules::GameMode,
+        side::Side,
+        venue::{Intensity, Requirement},
+    },
+    App, AppState,
+};
+
+pub const USAGE: &str = "\
+usage: cyberconnect [play] [SETUP] [--tick-rate MS]
+       cyberconnect simulate [SETUP] [--games N]
+       cyberconnect replay ID [--delay MS]
+       cyberconnect history [list|stats|show ID|delete ID] [...]
+
+Setup:
+  --for-players N, --against-players N   players of each team (1 to 3)
+  --mode target[:N]|rounds[:N]|timed[:MIN]
+  --sudden-death on|off
+  --set KEY=VALUE                        any rule of cyberconnect.conf, repeatable
+  --venue water,paper,juggling,sprint,indoors|none
+  --intensity calm|active|physical       with --venue, picks the challenges drawn
+  --packs duel,team                      challenge packs drawn from
+  --code CODE                            setup code of the board, starts straight in Play
+
+Everywhere: --lang en|fr, --theme NAME, --help";
+
+pub const DEFAULT_TICK_RATE: Duration = Duration::from_millis(100);
+// Every flag takes a value
+const FLAGS: &[&str] = &[
+    "--lang",
+    "--theme",
+    "--for-players",
+    "--against-players",
+    "--mode",
+    "--sudden-death",
+    "--set",
+    "--venue",
+    "--intensity",
+    "--packs",
+    "--code",
+    "--tick-rate",
+    "--games",
+    "--delay",
+    "--name",
+    "--result",
+];
+const COMMANDS: &[&str] = &["play", "simulate", "replay", "history"];
+// Games simulated without --games
+const DEFAULT_GAMES: u32 = 1;
+
+// Setup flags fill in the wizard, --mode is also a history filter
+fn applies(flag: &str, command: &str) -> bool {
+    match flag {
+        "--lang" | "--theme" => true,
+        "--tick-rate" => command == "play",
+        "--games" => command == "simulate",
+        "--delay" => command == "replay",
+        "--name" | "--result" => command == "history",
+        "--mode" => command != "replay",
+        _ => command == "play" || command == "simulate",
+    }
+}
+
+// What the setup flags fill in, the wizard asks for the rest
+#[derive(Default)]
+pub struct Setup {
+    pub for_players: Option<u8>,
+    pub against_players: Option<u8>,
+    pub mode: Option<GameMode>,
+    pub sudden_death: Option<bool>,
+    pub venue: Option<Vec<Requirement>>,
+    pub intensity: Option<Intensity>,
+    pub packs: Option<Vec<Category>>,
+    // Checked once the language is known, its error is translated
+    pub code: Option<String>,
+}
+
+pub enum Command {
+    Play { setup: Setup, tick_rate: Duration },
+    Simulate { setup: Setup, games: u32 },
+    Replay { id: u32, delay: Duration },
+    History(Vec<String>),
+    Help,
+}
+
+pub struct Cli {
+    pub lang: Option<String>,
+    pub theme: Option<String>,
+    // Rules given with --set, read like cyberconnect.conf lines
+    pub overrides: Vec<(String, String)>,
+    pub command: Command,
+}
+
+fn number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{}: not a number: {}", flag, value))
+}
+
+fn players(flag: &str, value: &str) -> Result<u8, String> {
+    let players = number(flag, value)?;
+    if !(MIN_PLAYERS..=MAX_PLAYERS).contains(&players) {
+        return Err(format!(
+            "{}: between {} and {} players",
+            flag, MIN_PLAYERS, MAX_PLAYERS
+        ));
+    }
+    Ok(players)
+}
+
+// Comma separated codes
+fn list<T>(flag: &str, value: &str, from_code: fn(&str) -> Option<T>) -> Result<Vec<T>, String> {
+    value
+        .split(',')
+        .map(|code| {
+            from_code(code.trim()).ok_or_else(|| format!("{}: unknown value: {}", flag, code))
+        })
+        .collect()
+}
+
+// Arguments without the program name, flags can come before or after the command
+pub fn parse(args: &[String]) -> Result<Cli, String> {
+    let mut flags = vec![];
+    let mut positional = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--help" || arg == "-h" {
+            return Ok(Cli {
+                lang: None,
+                theme: None,
+                overrides: vec![],
+                command: Command::Help,
+            });
+        }
+        if !arg.starts_with("--") {
+            positional.push(arg.clone());
+            continue;
+        }
+        if !FLAGS.contains(&arg.as_str()) {
+            return Err(format!("unknown flag: {}", arg));
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("{}: missing value", arg))?;
+        flags.push((arg.as_str(), value.clone()));
+    }
+
+    let (command, rest) = positional
+        .split_first()
+        .map_or(("play", &[][..]), |(command, rest)| {
+            (command.as_str(), rest)
+        });
+    if !COMMANDS.contains(&command) {
+        return Err(format!("unknown command: {}", command));
+    }
+
+    let mut cli = Cli {
+        lang: None,
+        theme: None,
+        overrides: vec![],
+        command: Command::Help,
+    };
+    let mut setup = Setup::default();
+    let mut tick_rate = DEFAULT_TICK_RATE;
+    let mut games = DEFAULT_GAMES;
+    let mut delay = Duration::default();
+    let mut filters = vec![];
+    for (flag, value) in flags {
+        if !applies(flag, command) {
+            return Err(format!("{} does not apply to {}", flag, command));
+        }
+        match flag {
+            "--lang" => cli.lang = Some(value),
+            "--theme" => cli.theme = Some(value),
+            "--name" | "--result" => filters.extend([flag.to_string(), value]),
+            "--mode" if command == "history" => filters.extend([flag.to_string(), value]),
+            "--mode" => {
+                setup.mode = Some(
+                    GameMode::from_key(&value)
+                        .ok_or_else(|| format!("{}: unknown mode: {}", flag, value))?,
+                )
+            }
+            "--for-players" => setup.for_players = Some(players(flag, &value)?),
+            "--against-players" => setup.against_players = Some(players(flag, &value)?),
+            "--sudden-death" => {
+                setup.sudden_death = Some(match value.as_str() {
+                    "on" => true,
+                    "off" => false,
+                    _ => return Err(format!("{}: on or off", flag)),
+                })
+            }
+            "--set" => {
+                let (key, value) = value
+                    .split_once('=')
+                    .ok_or_else(|| format!("{}: KEY=VALUE expected", flag))?;
+                cli.overrides
+                    .push((key.trim().to_string(), value.trim().to_string()));
+            }
+            "--venue" => {
+                setup.venue = Some(match value.as_str() {
+                    "none" => vec![],
+                    _ => list(flag, &value, Requirement::from_code)?,
+                })
+            }
+            "--intensity" => {
+                setup.intensity = Some(
+                    Intensity::from_code(&value)
+                        .ok_or_else(|| format!("{}: unknown intensity: {}", flag, value))?,
+                )
+            }
+            "--packs" => setup.packs = Some(list(flag, &value, Category::from_code)?),
+            "--code" => setup.code = Some(value),
+            "--tick-rate" => tick_rate = Duration::from_millis(number(flag, &value)?),
+            "--games" => games = number::<u32>(flag, &value)?.max(1),
+            "--delay" => delay = Duration::from_millis(number(flag, &value)?),
+            _ => unreachable!(),
+        }
+    }
+    if setup.code.is_some() && (setup.for_players.is_none() || setup.against_players.is_none()) {
+        return Err("--code needs --for-players and --against-players".to_string());
+    }
+
+    cli.command = match command {
+        "play" | "simulate" if !rest.is_empty() => {
+            return Err(format!("unexpected argument: {}", rest[0]))
+        }
+        "play" => Command::Play { setup, tick_rate },
+        "simulate" => Command::Simulate { setup, games },
+        "replay" => match rest {
+            [id] => Command::Replay {
+                id: number("replay", id.trim_start_matches('#'))?,
+                delay,
+            },
+            _ => return Err("replay needs the id of an archived game".to_string()),
+        },
+        _ => {
+            // Filters only narrow down lists
+            if let (Some(action), Some(filter)) = (rest.first(), filters.first()) {
+                if action != "list" && action != "stats" {
+                    return Err(format!("{} does not apply to history {}", filter, action));
+                }
+            }
+            Command::History([rest, &filters[..]].concat())
+        }
+    };
+    Ok(cli)
+}
+
+impl Setup {
+    // Sides and rules given on the command line
+    pub fn apply(&self, game: &mut Game) {
+        for (ai_side, players) in [
+            (AiSide::For, self.for_players),
+            (AiSide::Against, self.against_players),
+        ] {
+            if let Some(players) = players {
+                let side = Some(Side::new(players));
+                match ai_side {
+                    AiSide::For => game.for_ai = side,
+                    AiSide::Against => game.against_ai = side,
+                }
+            }
+        }
+        if let Some(mode) = &self.mode {
+            game.rules.mode = mode.clone();
+        }
+        if let Some(sudden_death) = self.sudden_death {
+            game.rules.sudden_death = sudden_death;
+        }
+        if let Some(venue) = &self.venue {
+            game.rules.venue.available = venue.clone();
+        }
+        if let Some(intensity) = self.intensity {
+            game.rules.venue.max_intensity = intensity;
+        }
+        if let Some(packs) = &self.packs {
+            game.rules.venue.packs = packs.clone();
+        }
+    }
+
+    pub fn board(&self) -> Result<Option<Board>, String> {
+        match &self.code {
+            Some(code) => Board::from_code(code)
+                .map(Some)
+                .map_err(|err| format!("--code: {}", err.description())),
+            None => Ok(None),
+        }
+    }
+}
+
+impl App<'_> {
+    // The wizard starts at the first step the flags left, or in Play when the board is given
+    pub fn with_setup(setup: &Setup) -> Result<App<'static>, String> {
+        let mut app = App::new();
+        setup.apply(&mut app.game);
+        app.state = match (&app.game.for_ai, &app.game.against_ai) {
+            (None, _) => AppState::PlayerInput(AiSide::For),
+            (Some(_), None) => AppState::PlayerInput(AiSide::Against),
+            (Some(_), Some(_)) => AppState::RosterInput,
+        };
+        if let Some(board) = setup.board()? {
+            app.game.suggest_handicaps();
+            app.game.place_vpns(&board);
+            app.game.place_captures(&board);
+            app.game.begin();
+            app.board = Some(board);
+            app.state = AppState::Play;
+        }
+        Ok(app)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(args: &str) -> Result<Cli, String> {
+        let args: Vec<String> = args.split_whitespace().map(str::to_string).collect();
+        parse(&args)
+    }
+
+    #[test]
+    fn flags_come_before_or_after_the_command() {
+        for args in [
+            "history --name Ada --result draw",
+            "--name Ada history --result draw",
+            "--result draw --name Ada history list",
+        ] {
+            match parsed(args).map(|cli| cli.command) {
+                Ok(Command::History(args)) => {
+                    assert!(args.windows(2).any(|pair| pair == ["--name", "Ada"]));
+                    assert!(args.windows(2).any(|pair| pair == ["--result", "draw"]));
+                }
+                _ => panic!("{} is not a history command", args),
+            }
+        }
+    }
+
+    #[test]
+    fn flags_of_other_commands_are_refused() {
+        assert!(parsed("history --for-players 2").is_err());
+        assert!(parsed("history --tick-rate 50").is_err());
+        assert!(parsed("history show 3 --name Ada").is_err());
+        assert!(parsed("replay 3 --games 2").is_err());
+        assert!(parsed("play --delay 10").is_err());
+        assert!(parsed("simulate --name Ada").is_err());
+        assert!(parsed("--bogus 1").is_err());
+        assert!(parsed("dance").is_err());
+    }
+
+    #[test]
+    fn setup_flags_fill_in_the_setup() {
+        let cli = parsed(
+            "--lang fr simulate --games 4 --for-players 3 --packs team,duel --mode rounds:8",
+        )
+        .unwrap();
+        assert_eq!(cli.lang.as_deref(), Some("fr"));
+        match cli.command {
+            Command::Simulate { setup, games } => {
+                assert_eq!(games, 4);
+                assert_eq!(setup.for_players, Some(3));
+                assert!(setup.packs == Some(vec![Category::Team, Category::Duel]));
+                assert!(setup.mode == Some(GameMode::Rounds(8)));
+            }
+            _ => panic!("not a simulation"),
+        }
+        assert!(parsed("--packs solo").is_err());
+        assert!(parsed("--for-players 4").is_err());
+        assert!(parsed("--code 0FD5F05 --for-players 2").is_err());
+    }
+
+    #[test]
+    fn play_is_the_default_command() {
+        assert!(matches!(
+            parsed("--for-players 2").map(|cli| cli.command),
+            Ok(Command::Play { .. })
+        ));
+        assert!(matches!(
+            parsed("--help history").map(|cli| cli.command),
+            Ok(Command::Help)
+        ));
+    }
+}
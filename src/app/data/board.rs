@@ -0,0 +1,404 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    capture::{check_capture_position, Location},
+    game::{AiSide, PlacementError, Position, COL_RANGE, ROW_RANGE},
+    race::{manhattan, walk, Steps, WALLS},
+    vpn::check_vpn_position,
+};
+use crate::config;
+
+pub const DEFAULT_SPACING: u8 = 2;
+pub const MAX_SPACING: u8 = 4;
+const DEFAULT_CENTER_REACH: u8 = 10;
+
+// Setup codes write the cell of every feature in base 32, without the letters I, L, O and U
+const CODE_ALPHABET: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+pub const CODE_LEN: usize = 7;
+const CELLS: u64 = *ROW_RANGE.end() as u64 * *COL_RANGE.end() as u64;
+
+// Something placed on the board during setup
+#[derive(Clone, PartialEq)]
+pub enum Feature {
+    Vpn(AiSide),
+    Capture(Location),
+}
+
+// Every feature, in the order they are generated
+pub const FEATURES: &[Feature] = &[
+    Feature::Vpn(AiSide::For),
+    Feature::Vpn(AiSide::Against),
+    Feature::Capture(Location::Home(AiSide::For)),
+    Feature::Capture(Location::Home(AiSide::Against)),
+    Feature::Capture(Location::Center),
+];
+
+impl Feature {
+    // Board rules every position of the feature follows
+    pub fn check(&self, position: &Position) -> Result<(), PlacementError> {
+        match self {
+            Feature::Vpn(ai_side) => check_vpn_position(ai_side, position),
+            Feature::Capture(location) => check_capture_position(location, position),
+        }
+    }
+
+    // Same feature of the other team, None for the center
+    fn mirror(&self) -> Option<Feature> {
+        match self {
+            Feature::Vpn(ai_side) => Some(Feature::Vpn(ai_side.switch())),
+            Feature::Capture(Location::Home(ai_side)) => {
+                Some(Feature::Capture(Location::Home(ai_side.switch())))
+            }
+            Feature::Capture(Location::Center) => None,
+        }
+    }
+}
+
+impl Position {
+    // Cells counted row by row from A1
+    fn index(&self) -> u64 {
+        (self.row - ROW_RANGE.start()) as u64 * *COL_RANGE.end() as u64
+            + (self.col - COL_RANGE.start()) as u64
+    }
+
+    fn from_index(index: u64) -> Position {
+        Position {
+            row: (index / *COL_RANGE.end() as u64) as u8 + ROW_RANGE.start(),
+            col: (index % *COL_RANGE.end() as u64) as u8 + COL_RANGE.start(),
+        }
+    }
+
+    // Same cell seen from the other team's half
+    fn mirrored(&self) -> Position {
+        Position {
+            row: self.row,
+            col: COL_RANGE.start() + COL_RANGE.end() - self.col,
+        }
+    }
+}
+
+// What a generated board has to respect, typed positions only follow the board rules
+pub struct Constraints {
+    // Fewest steps between two features
+    pub spacing: u8,
+    // The Against AI features mirror the For AI ones
+    pub symmetric: bool,
+    // Most steps from either base to the center point, around the middle walls
+    pub center_reach: u8,
+}
+
+impl Constraints {
+    pub fn new() -> Constraints {
+        Constraints {
+            spacing: config::value("board_spacing")
+                .and_then(|spacing| spacing.parse().ok())
+                .map_or(DEFAULT_SPACING, |spacing: u8| spacing.clamp(1, MAX_SPACING)),
+            symmetric: config::value("board_symmetry").as_deref() == Some("on"),
+            center_reach: config::value("center_reach")
+                .and_then(|steps| steps.parse().ok())
+                .unwrap_or(DEFAULT_CENTER_REACH),
+        }
+    }
+
+    pub fn next_spacing(&mut self) {
+        self.spacing = self.spacing % MAX_SPACING + 1;
+    }
+}
+
+// Steps from each base to every cell
+struct Reach(Steps, Steps);
+
+impl Reach {
+    fn new() -> Reach {
+        Reach(walk(&[AiSide::For.base()]), walk(&[AiSide::Against.base()]))
+    }
+
+    fn within(&self, position: &Position, steps: u8) -> bool {
+        [&self.0, &self.1].iter().all(|walk| {
+            walk[position.row as usize][position.col as usize].is_some_and(|step| step <= steps)
+        })
+    }
+}
+
+// What stands on a cell of the printed board
+pub enum Cell {
+    Empty,
+    Wall,
+    Base(AiSide),
+    Feature(Feature),
+}
+
+#[derive(Clone)]
+pub struct Board(Vec<(Feature, Position)>);
+
+impl Board {
+    // Whole setup at once, or NoRoom when the constraints cannot be met
+    pub fn generate<R: Rng>(
+        rng: &mut R,
+        constraints: &Constraints,
+    ) -> Result<Board, PlacementError> {
+        let mut board = Board(vec![]);
+        if board.fill(rng, constraints, &Reach::new()) {
+            Ok(board)
+        } else {
+            Err(PlacementError::NoRoom)
+        }
+    }
+
+    pub fn get(&self, feature: &Feature) -> Option<Position> {
+        self.0
+            .iter()
+            .find(|(placed, _)| placed == feature)
+            .map(|(_, position)| *position)
+    }
+
+    pub fn set(&mut self, feature: &Feature, position: Position) {
+        match self.0.iter_mut().find(|(placed, _)| placed == feature) {
+            Some((_, placed)) => *placed = position,
+            None => self.0.push((feature.clone(), position)),
+        }
+    }
+
+    // Typed positions follow the board rules and stay off the other features
+    pub fn check(&self, feature: &Feature, position: &Position) -> Result<(), PlacementError> {
+        feature.check(position)?;
+        if self
+            .0
+            .iter()
+            .any(|(placed, other)| placed != feature && other == position)
+        {
+            return Err(PlacementError::Taken);
+        }
+        Ok(())
+    }
+
+    // Another position for one feature, its mirror follows on a symmetric board
+    pub fn reroll<R: Rng>(
+        &mut self,
+        rng: &mut R,
+        feature: &Feature,
+        constraints: &Constraints,
+    ) -> Result<(), PlacementError> {
+        let mirror = feature.mirror().filter(|_| constraints.symmetric);
+        let mut rest = Board(
+            self.0
+                .iter()
+                .filter(|(placed, _)| placed != feature && Some(placed) != mirror.as_ref())
+                .cloned()
+                .collect(),
+        );
+        let reach = Reach::new();
+        let mut candidates = rest.candidates(rng, feature, constraints, &reach);
+        // The current position only when nothing else fits
+        let current = self.get(feature);
+        candidates.sort_by_key(|position| Some(*position) == current);
+        for position in candidates {
+            if let Some(mirror) = &mirror {
+                if !rest.fits(mirror, &position.mirrored(), constraints, &reach) {
+                    continue;
+                }
+                rest.set(mirror, position.mirrored());
+            }
+            rest.set(feature, position);
+            *self = rest;
+            return Ok(());
+        }
+        Err(PlacementError::NoRoom)
+    }
+
+    // Short code to note down, giving back the same layout with from_code
+    pub fn code(&self) -> String {
+        let mut value = FEATURES.iter().rev().fold(0, |value, feature| {
+            value * CELLS + self.get(feature).map_or(0, |position| position.index())
+        });
+        let mut code = vec![];
+        for _ in 0..CODE_LEN {
+            code.push(CODE_ALPHABET.as_bytes()[(value % 32) as usize] as char);
+            value /= 32;
+        }
+        code.iter().rev().collect()
+    }
+
+    // Dashes and spaces are ignored, O reads as 0 and I or L as 1
+    pub fn from_code(code: &str) -> Result<Board, PlacementError> {
+        let mut value: u64 = 0;
+        let mut len = 0;
+        for c in code.chars().filter(|c| !matches!(c, '-' | ' ')) {
+            let c = match c.to_ascii_uppercase() {
+                'O' => '0',
+                'I' | 'L' => '1',
+                c => c,
+            };
+            let digit = CODE_ALPHABET.find(c).ok_or(PlacementError::Code)?;
+            value = value * 32 + digit as u64;
+            len += 1;
+        }
+        if len != CODE_LEN || value >= CELLS.pow(FEATURES.len() as u32) {
+            return Err(PlacementError::Code);
+        }
+        let mut board = Board(vec![]);
+        for feature in FEATURES {
+            let position = Position::from_index(value % CELLS);
+            value /= CELLS;
+            board.check(feature, &position)?;
+            board.set(feature, position);
+        }
+        Ok(board)
+    }
+
+    // Rows of cells from row 1, each from column A
+    pub fn grid(&self) -> Vec<Vec<Cell>> {
+        ROW_RANGE
+            .map(|row| {
+                COL_RANGE
+                    .map(|col| {
+                        let position = Position { row, col };
+                        let base = [AiSide::For, AiSide::Against]
+                            .into_iter()
+                            .find(|ai_side| ai_side.base() == position);
+                        match (self.0.iter().find(|(_, placed)| *placed == position), base) {
+                            (Some((feature, _)), _) => Cell::Feature(feature.clone()),
+                            (None, Some(ai_side)) => Cell::Base(ai_side),
+                            (None, None) if WALLS.contains(&position) => Cell::Wall,
+                            (None, None) => Cell::Empty,
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // Places the features left in order, going back when one has no room
+    fn fill<R: Rng>(&mut self, rng: &mut R, constraints: &Constraints, reach: &Reach) -> bool {
+        let feature = match FEATURES.get(self.0.len()) {
+            Some(feature) => feature.clone(),
+            None => return true,
+        };
+        for position in self.candidates(rng, &feature, constraints, reach) {
+            self.0.push((feature.clone(), position));
+            if self.fill(rng, constraints, reach) {
+                return true;
+            }
+            self.0.pop();
+        }
+        false
+    }
+
+    // Shuffled cells the feature can take next to the features already placed
+    fn candidates<R: Rng>(
+        &self,
+        rng: &mut R,
+        feature: &Feature,
+        constraints: &Constraints,
+        reach: &Reach,
+    ) -> Vec<Position> {
+        let mirrored = feature
+            .mirror()
+            .filter(|_| constraints.symmetric)
+            .and_then(|mirror| self.get(&mirror))
+            .map(|position| position.mirrored());
+        let mut cells: Vec<Position> = match mirrored {
+            Some(position) => vec![position],
+            None => ROW_RANGE
+                .flat_map(|row| COL_RANGE.map(move |col| Position { row, col }))
+                .collect(),
+        };
+        cells.retain(|position| self.fits(feature, position, constraints, reach));
+        cells.shuffle(rng);
+        cells
+    }
+
+    fn fits(
+        &self,
+        feature: &Feature,
+        position: &Position,
+        constraints: &Constraints,
+        reach: &Reach,
+    ) -> bool {
+        feature.check(position).is_ok()
+            && self.0.iter().all(|(placed, other)| {
+                placed == feature || manhattan(other, position) >= constraints.spacing.max(1)
+            })
+            && (*feature != Feature::Capture(Location::Center)
+                || reach.within(position, constraints.center_reach))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn constraints() -> Constraints {
+        Constraints {
+            spacing: DEFAULT_SPACING,
+            symmetric: false,
+            center_reach: DEFAULT_CENTER_REACH,
+        }
+    }
+
+    #[test]
+    fn codes_give_back_the_same_board() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..50 {
+            let board = Board::generate(&mut rng, &constraints()).unwrap();
+            let code = board.code();
+            assert_eq!(code.len(), CODE_LEN);
+            let read = Board::from_code(&code).unwrap();
+            for feature in FEATURES {
+                assert!(read.get(feature) == board.get(feature));
+            }
+            assert_eq!(read.code(), code);
+        }
+    }
+
+    #[test]
+    fn codes_forgive_dashes_case_and_lookalikes() {
+        let board = Board::generate(&mut StdRng::seed_from_u64(3), &constraints()).unwrap();
+        let code = board.code();
+        let noted = format!("{}-{}", &code[..3], &code[3..])
+            .to_lowercase()
+            .replace('0', "o")
+            .replace('1', "l");
+        assert_eq!(Board::from_code(&noted).unwrap().code(), code);
+    }
+
+    #[test]
+    fn bad_codes_are_refused() {
+        let code = Board::generate(&mut StdRng::seed_from_u64(5), &constraints())
+            .unwrap()
+            .code();
+        assert!(matches!(
+            Board::from_code(&code[1..]),
+            Err(PlacementError::Code)
+        ));
+        assert!(matches!(
+            Board::from_code(&format!("{}0", code)),
+            Err(PlacementError::Code)
+        ));
+        assert!(matches!(
+            Board::from_code("U000000"),
+            Err(PlacementError::Code)
+        ));
+        assert!(matches!(
+            Board::from_code("ZZZZZZZ"),
+            Err(PlacementError::Code)
+        ));
+        // Every feature on A1
+        assert!(Board::from_code("0000000").is_err());
+    }
+
+    #[test]
+    fn symmetric_boards_mirror_the_features() {
+        let constraints = Constraints {
+            symmetric: true,
+            ..constraints()
+        };
+        let board = Board::generate(&mut StdRng::seed_from_u64(11), &constraints).unwrap();
+        for feature in FEATURES {
+            if let Some(mirror) = feature.mirror() {
+                assert!(board.get(&mirror) == board.get(feature).map(|p| p.mirrored()));
+            }
+        }
+    }
+}
@@ -1,8 +1,11 @@
-use rand::{rngs::ThreadRng, Rng};
+use std::cmp::Ordering;
 
-use super::game::{AiSide, Position};
+use super::{
+    board::{Board, Feature},
+    game::{AiSide, Game, PlacementError, Position},
+};
 
-pub const ILLEGAL_CAPTURE_POSITIONS: &'static [Position] = &[
+pub const ILLEGAL_CAPTURE_POSITIONS: &[Position] = &[
     Position { row: 4, col: 1 },  // Base
     Position { row: 4, col: 11 }, // Base
     Position { row: 3, col: 6 },  // Middle
@@ -13,31 +16,176 @@ pub const ILLEGAL_CAPTURE_POSITIONS: &'static [Position] = &[
 pub const CENTER_CAPTURE_MULTIPLIER: f32 = 1.3;
 pub const CAPTURE_POINTS: u32 = 2;
 
-pub struct Capture {
-    count: u32,
-    pub ai_side: AiSide,
+#[derive(Clone, PartialEq)]
+pub enum Location {
+    // Capture point on a team's half
+    Home(AiSide),
+    Center,
 }
 
-impl Capture {
-    pub fn new(ai_side: AiSide) -> Capture {
-        Capture { count: 0, ai_side }
+// How a capture point changes hands
+#[derive(Clone, Copy, PartialEq)]
+pub enum Transfer {
+    // The team with the most occupants owns it
+    Majority,
+    // The owner keeps it until the other team is alone on it
+    Exclusive,
+}
+
+impl Transfer {
+    pub fn next(&self) -> Transfer {
+        match self {
+            Transfer::Majority => Transfer::Exclusive,
+            Transfer::Exclusive => Transfer::Majority,
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Transfer> {
+        match code {
+            "majority" => Some(Transfer::Majority),
+            "exclusive" => Some(Transfer::Exclusive),
+            _ => None,
+        }
+    }
+}
+
+pub struct CapturePoint {
+    pub location: Location,
+    // Where it stands on the board, once generated
+    pub position: Option<Position>,
+    for_ai: u32,
+    against_ai: u32,
+    owner: Option<AiSide>,
+}
+
+impl CapturePoint {
+    pub fn new(location: Location) -> CapturePoint {
+        CapturePoint {
+            location,
+            position: None,
+            for_ai: 0,
+            against_ai: 0,
+            owner: None,
+        }
+    }
+
+    pub fn occupants(&self, ai_side: &AiSide) -> u32 {
+        match ai_side {
+            AiSide::For => self.for_ai,
+            AiSide::Against => self.against_ai,
+        }
     }
 
-    pub fn count(&self) -> u32 {
-        self.count
+    pub fn owner(&self) -> Option<&AiSide> {
+        self.owner.as_ref()
     }
 
-    pub fn add(&mut self) {
-        self.count += 1;
+    // Both teams are on it, nobody scores
+    pub fn is_contested(&self) -> bool {
+        self.for_ai > 0 && self.against_ai > 0
+    }
+
+    pub fn add(&mut self, ai_side: AiSide, transfer: Transfer) {
+        match ai_side {
+            AiSide::For => self.for_ai += 1,
+            AiSide::Against => self.against_ai += 1,
+        }
+        self.transfer(transfer);
+    }
+
+    pub fn remove(&mut self, ai_side: AiSide, transfer: Transfer) {
+        match ai_side {
+            AiSide::For => self.for_ai = self.for_ai.saturating_sub(1),
+            AiSide::Against => self.against_ai = self.against_ai.saturating_sub(1),
+        }
+        self.transfer(transfer);
     }
 
-    pub fn remove(&mut self) {
-        if self.count >= 1 {
-            self.count -= 1;
+    fn transfer(&mut self, transfer: Transfer) {
+        let leader = match self.for_ai.cmp(&self.against_ai) {
+            Ordering::Greater => Some(AiSide::For),
+            Ordering::Less => Some(AiSide::Against),
+            Ordering::Equal => None,
+        };
+        match transfer {
+            // A tie keeps the owner, an empty point has none
+            Transfer::Majority => {
+                if self.for_ai + self.against_ai == 0 {
+                    self.owner = None;
+                } else if leader.is_some() {
+                    self.owner = leader;
+                }
+            }
+            Transfer::Exclusive => {
+                if !self.is_contested() && leader.is_some() {
+                    self.owner = leader;
+                }
+            }
+        }
+    }
+
+    // Occupants counting for ai_side at the end of its turn
+    pub fn held(&self, ai_side: &AiSide) -> u32 {
+        if self.owner.as_ref() == Some(ai_side) && !self.is_contested() {
+            self.occupants(ai_side)
+        } else {
+            0
         }
     }
 }
 
+impl Game {
+    pub fn capture(&self, location: &Location) -> Option<&CapturePoint> {
+        self.captures
+            .iter()
+            .find(|point| point.location == *location)
+    }
+
+    pub fn capture_mut(&mut self, location: &Location) -> Option<&mut CapturePoint> {
+        self.captures
+            .iter_mut()
+            .find(|point| point.location == *location)
+    }
+
+    pub fn place_captures(&mut self, board: &Board) {
+        for point in self.captures.iter_mut() {
+            point.position = board.get(&Feature::Capture(point.location.clone()));
+        }
+    }
+
+    pub fn add_capture(&mut self, location: &Location, ai_side: AiSide) {
+        let transfer = self.rules.capture_transfer;
+        if !self.is_frozen(location) {
+            if let Some(point) = self.capture_mut(location) {
+                point.add(ai_side, transfer);
+            }
+        }
+    }
+
+    pub fn remove_capture(&mut self, location: &Location, ai_side: AiSide) {
+        let transfer = self.rules.capture_transfer;
+        if !self.is_frozen(location) {
+            if let Some(point) = self.capture_mut(location) {
+                point.remove(ai_side, transfer);
+            }
+        }
+    }
+
+    // Capture points scored by ai_side at the end of a turn, frozen points score nothing
+    // Points of the home points and of the center one, earned at the start of the team's turn
+    pub fn capture_points(&self, ai_side: &AiSide) -> (u32, u32) {
+        let held = |location: &Location| {
+            self.capture(location)
+                .filter(|_| !self.is_frozen(location))
+                .map_or(0, |point| point.held(ai_side))
+        };
+        let homes = held(&Location::Home(AiSide::For)) + held(&Location::Home(AiSide::Against));
+        let center = (CENTER_CAPTURE_MULTIPLIER * held(&Location::Center) as f32).round() as u32;
+        let points = CAPTURE_POINTS * self.capture_multiplier();
+        (points * homes, points * center)
+    }
+}
+
 impl AiSide {
     fn pos_inside(&self, pos: &Position) -> bool {
         match self {
@@ -47,14 +195,20 @@ impl AiSide {
     }
 }
 
-pub fn capture_position(rng: &mut ThreadRng, ai_side: AiSide) -> Position {
-    loop {
-        let position = rng.gen();
-        if !ILLEGAL_CAPTURE_POSITIONS.contains(&position)
-            && (position.col < 5 || position.col > 7)
-            && ai_side.pos_inside(&position)
-        {
-            return position;
-        }
+// The center point can stand on either half
+pub fn check_capture_position(
+    location: &Location,
+    position: &Position,
+) -> Result<(), PlacementError> {
+    if ILLEGAL_CAPTURE_POSITIONS.contains(position) {
+        return Err(PlacementError::Illegal);
+    }
+    let inside = match location {
+        Location::Home(ai_side) => ai_side.pos_inside(position),
+        Location::Center => true,
+    };
+    if (5..=7).contains(&position.col) || !inside {
+        return Err(PlacementError::WrongHalf);
     }
+    Ok(())
 }
@@ -0,0 +1,621 @@
+use std::{
+    cmp::Ordering,
+    time::{Duration, Instant},
+};
+
+use rand::{distributions::WeightedIndex, prelude::Distribution, seq::SliceRandom, Rng};
+
+use super::{
+    game::{AiSide, Game, CHALLENGE_POINTS},
+    side::Side,
+    venue::{Intensity, Requirement, Venue},
+};
+use crate::app::i18n::{self, tr};
+
+// Times a reroll is attempted before keeping the same fill-ins
+const REROLL_ATTEMPTS: usize = 10;
+
+// Drawing weights, the previous challenge's category is less likely and so are
+// challenges played less than RECENCY_WINDOW draws ago
+const SAME_CATEGORY_WEIGHT: f64 = 0.5;
+const RECENCY_WINDOW: usize = 10;
+
+// Manual timer adjustments
+const DURATION_STEP: Duration = Duration::from_secs(10);
+const MAX_DURATION: Duration = Duration::from_secs(10 * 60);
+
+pub const MAX_CHALLENGE_POINTS: u32 = 20;
+
+// Also the challenge packs a game can draw from
+#[derive(Clone, Copy, PartialEq)]
+pub enum Category {
+    Duel,
+    Team,
+}
+
+pub const CATEGORIES: &[Category] = &[Category::Duel, Category::Team];
+
+impl Category {
+    pub fn from_code(code: &str) -> Option<Category> {
+        match code {
+            "duel" => Some(Category::Duel),
+            "team" => Some(Category::Team),
+            _ => None,
+        }
+    }
+}
+
+pub struct ChallengeInfo {
+    // i18n key of the challenge template
+    pub key: &'static str,
+    pub duration: Option<Duration>,
+    pub points: u32,
+    pub needs: &'static [Requirement],
+    pub intensity: Intensity,
+    // Subjective, the players not taking part can vote for the winner
+    pub judged: bool,
+}
+
+const fn untimed(key: &'static str, points: u32) -> ChallengeInfo {
+    ChallengeInfo {
+        key,
+        duration: None,
+        points,
+        needs: &[],
+        intensity: Intensity::Calm,
+        judged: false,
+    }
+}
+
+const fn timed(key: &'static str, secs: u64, points: u32) -> ChallengeInfo {
+    ChallengeInfo {
+        duration: Some(Duration::from_secs(secs)),
+        ..untimed(key, points)
+    }
+}
+
+impl ChallengeInfo {
+    const fn needs(self, needs: &'static [Requirement]) -> ChallengeInfo {
+        ChallengeInfo { needs, ..self }
+    }
+
+    const fn intensity(self, intensity: Intensity) -> ChallengeInfo {
+        ChallengeInfo { intensity, ..self }
+    }
+
+    const fn judged(self) -> ChallengeInfo {
+        ChallengeInfo {
+            judged: true,
+            ..self
+        }
+    }
+
+    pub fn is_playable(&self, venue: &Venue) -> bool {
+        self.intensity <= venue.max_intensity && self.needs.iter().all(|need| venue.has(*need))
+    }
+}
+
+// Challenge texts live in the i18n catalogs
+pub const SOLO_CHALLENGES: &[ChallengeInfo] = &[
+    untimed("duel.shifumi", 2),
+    untimed("duel.one_foot", CHALLENGE_POINTS).intensity(Intensity::Active),
+    untimed("duel.jump", CHALLENGE_POINTS).intensity(Intensity::Physical),
+    untimed("duel.paper_plane", CHALLENGE_POINTS)
+        .needs(&[Requirement::Paper])
+        .judged(),
+    untimed("duel.find_object", CHALLENGE_POINTS).intensity(Intensity::Active),
+    untimed("duel.thumb_war", CHALLENGE_POINTS),
+    untimed("duel.name_things", CHALLENGE_POINTS),
+    untimed("duel.water", CHALLENGE_POINTS)
+        .needs(&[Requirement::Water])
+        .intensity(Intensity::Active),
+    untimed("duel.tongue_twister", CHALLENGE_POINTS),
+    untimed("duel.juggle", CHALLENGE_POINTS)
+        .needs(&[Requirement::JugglingItems])
+        .intensity(Intensity::Active),
+    untimed("duel.coin_flip", 1),
+    timed("duel.draw", 30, CHALLENGE_POINTS)
+        .needs(&[Requirement::Paper])
+        .judged(),
+    untimed("duel.touch_foot", CHALLENGE_POINTS).intensity(Intensity::Physical),
+    untimed("duel.staring", CHALLENGE_POINTS),
+    untimed("duel.count_tiles", CHALLENGE_POINTS),
+    untimed("duel.sprint", CHALLENGE_POINTS)
+        .needs(&[Requirement::SprintSpace])
+        .intensity(Intensity::Physical),
+    untimed("duel.push_ups", CHALLENGE_POINTS).intensity(Intensity::Physical),
+    untimed("duel.impression", CHALLENGE_POINTS).judged(),
+];
+
+pub const TEAM_CHALLENGES: &[ChallengeInfo] = &[
+    untimed("team.socks", CHALLENGE_POINTS).intensity(Intensity::Active),
+    timed("team.tower", 60, 5).intensity(Intensity::Active),
+    untimed("team.name_three", CHALLENGE_POINTS),
+    untimed("team.raise_limb", CHALLENGE_POINTS).intensity(Intensity::Active),
+    untimed("team.ceiling", CHALLENGE_POINTS)
+        .needs(&[Requirement::Indoors])
+        .intensity(Intensity::Physical),
+    untimed("team.leave_room", CHALLENGE_POINTS)
+        .needs(&[Requirement::Indoors])
+        .intensity(Intensity::Physical),
+    untimed("team.off_ground", CHALLENGE_POINTS).intensity(Intensity::Physical),
+    untimed("team.circle", CHALLENGE_POINTS).intensity(Intensity::Active),
+    untimed("team.knees", CHALLENGE_POINTS).intensity(Intensity::Active),
+    untimed("team.poem", CHALLENGE_POINTS),
+    timed("team.letters", 60, 5),
+];
+
+#[derive(Clone, PartialEq)]
+pub enum ChallengeOutcome {
+    Won(AiSide),
+    // Both teams get half of the points
+    Draw,
+    BothFailed,
+}
+
+// How a tied vote is settled
+#[derive(Clone, Copy, PartialEq)]
+pub enum TieRule {
+    Draw,
+    // The referee decides with the usual keys
+    Referee,
+    // The team with the least progress wins, a draw when level
+    Underdog,
+}
+
+impl TieRule {
+    pub fn next(&self) -> TieRule {
+        match self {
+            TieRule::Draw => TieRule::Referee,
+            TieRule::Referee => TieRule::Underdog,
+            TieRule::Underdog => TieRule::Draw,
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<TieRule> {
+        match code {
+            "draw" => Some(TieRule::Draw),
+            "referee" => Some(TieRule::Referee),
+            "underdog" => Some(TieRule::Underdog),
+            _ => None,
+        }
+    }
+}
+
+// Ballots of the players not taking part in a judged challenge, cast in turn
+#[derive(Clone, PartialEq)]
+pub struct Vote {
+    pub voters: u32,
+    pub for_ai: u32,
+    pub against_ai: u32,
+    pub abstentions: u32,
+}
+
+impl Vote {
+    pub fn new(voters: u32) -> Vote {
+        Vote {
+            voters,
+            for_ai: 0,
+            against_ai: 0,
+            abstentions: 0,
+        }
+    }
+
+    // None abstains
+    pub fn cast(&mut self, ballot: Option<AiSide>) {
+        if self.is_complete() {
+            return;
+        }
+        match ballot {
+            Some(AiSide::For) => self.for_ai += 1,
+            Some(AiSide::Against) => self.against_ai += 1,
+            None => self.abstentions += 1,
+        }
+    }
+
+    pub fn cast_count(&self) -> u32 {
+        self.for_ai + self.against_ai + self.abstentions
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.cast_count() >= self.voters
+    }
+
+    // None when the referee has to decide
+    pub fn verdict(&self, rule: TieRule, game: &Game) -> Option<ChallengeOutcome> {
+        if self.for_ai > self.against_ai {
+            return Some(ChallengeOutcome::Won(AiSide::For));
+        }
+        if self.against_ai > self.for_ai {
+            return Some(ChallengeOutcome::Won(AiSide::Against));
+        }
+        match rule {
+            TieRule::Draw => Some(ChallengeOutcome::Draw),
+            TieRule::Referee => None,
+            TieRule::Underdog => {
+                let progress = |side: &Option<Side>| side.as_ref().map_or(0, Side::progress);
+                Some(
+                    match progress(&game.for_ai).cmp(&progress(&game.against_ai)) {
+                        Ordering::Less => ChallengeOutcome::Won(AiSide::For),
+                        Ordering::Greater => ChallengeOutcome::Won(AiSide::Against),
+                        Ordering::Equal => ChallengeOutcome::Draw,
+                    },
+                )
+            }
+        }
+    }
+}
+
+// Challenge played this session
+pub struct Played {
+    pub text: String,
+    // None when cancelled
+    pub outcome: Option<ChallengeOutcome>,
+    pub team_names: Option<(String, String)>,
+}
+
+// Shuffled deck, every challenge is drawn once before any repeat
+#[derive(Default)]
+pub struct Deck {
+    // Every draw of the session, the current cycle starting at cycle_start
+    draws: Vec<&'static str>,
+    cycle_start: usize,
+    pub banned: Vec<&'static str>,
+    pub history: Vec<Played>,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Challenge {
+    pub key: &'static str,
+    pub text: String,
+    pub points: u32,
+    pub duration: Option<Duration>,
+    start: Option<Instant>,
+    pub judged: bool,
+    pub vote: Option<Vote>,
+}
+
+impl Challenge {
+    pub fn new(info: &ChallengeInfo) -> Challenge {
+        Challenge {
+            key: info.key,
+            text: fill_in(tr!(info.key), &mut rand::thread_rng()),
+            points: info.points,
+            duration: info.duration,
+            start: None,
+            judged: info.judged,
+            vote: None,
+        }
+    }
+
+    pub fn is_template(&self) -> bool {
+        tr!(self.key).contains('{')
+    }
+
+    // Draws new fill-ins, keeping the same challenge
+    pub fn reroll(&mut self) {
+        let mut rng = rand::thread_rng();
+        for _ in 0..REROLL_ATTEMPTS {
+            let text = fill_in(tr!(self.key), &mut rng);
+            if text != self.text {
+                self.text = text;
+                return;
+            }
+        }
+    }
+
+    pub fn increase_points(&mut self) {
+        self.points = (self.points + 1).min(MAX_CHALLENGE_POINTS);
+    }
+
+    pub fn decrease_points(&mut self) {
+        self.points = self.points.saturating_sub(1);
+    }
+
+    // Points of each team
+    pub fn rewards(&self, outcome: &ChallengeOutcome) -> (u32, u32) {
+        match outcome {
+            ChallengeOutcome::Won(AiSide::For) => (self.points, 0),
+            ChallengeOutcome::Won(AiSide::Against) => (0, self.points),
+            ChallengeOutcome::Draw => (self.points / 2, self.points / 2),
+            ChallengeOutcome::BothFailed => (0, 0),
+        }
+    }
+
+    // The duration can only be changed while the timer is stopped
+    pub fn increase_duration(&mut self) {
+        if self.start.is_none() {
+            self.duration =
+                Some((self.duration.unwrap_or_default() + DURATION_STEP).min(MAX_DURATION));
+        }
+    }
+
+    pub fn decrease_duration(&mut self) {
+        if self.start.is_none() {
+            self.duration = self
+                .duration
+                .map(|duration| duration.saturating_sub(DURATION_STEP))
+                .filter(|duration| !duration.is_zero());
+        }
+    }
+
+    // Starts the countdown, or stops and resets it when running
+    pub fn toggle_timer(&mut self) {
+        self.start = match self.start {
+            None if self.duration.is_some() => Some(Instant::now()),
+            _ => None,
+        };
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.start.is_some()
+    }
+
+    pub fn remaining_time(&self) -> Option<chrono::Duration> {
+        let duration = self.duration?;
+        let took = self.start.map_or(Duration::ZERO, |start| start.elapsed());
+        chrono::Duration::from_std(duration.saturating_sub(took)).ok()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_running() && self.remaining_time().is_some_and(|time| time.is_zero())
+    }
+}
+
+// Challenges the venue allows, team challenges only when asked for.
+// Duels stay in when the chosen packs leave nothing to draw
+pub fn playable(venue: &Venue, teams: bool) -> Vec<(&'static ChallengeInfo, Category)> {
+    let allowed: Vec<(&ChallengeInfo, Category)> = SOLO_CHALLENGES
+        .iter()
+        .map(|info| (info, Category::Duel))
+        .chain(
+            TEAM_CHALLENGES
+                .iter()
+                .filter(|_| teams)
+                .map(|info| (info, Category::Team)),
+        )
+        .filter(|(info, _)| info.is_playable(venue))
+        .collect();
+    let packed: Vec<(&ChallengeInfo, Category)> = allowed
+        .iter()
+        .filter(|(_, category)| venue.packs.contains(category))
+        .copied()
+        .collect();
+    if packed.is_empty() {
+        allowed
+            .into_iter()
+            .filter(|(_, category)| *category == Category::Duel)
+            .collect()
+    } else {
+        packed
+    }
+}
+
+fn category_of(key: &str) -> Category {
+    if TEAM_CHALLENGES.iter().any(|info| info.key == key) {
+        Category::Team
+    } else {
+        Category::Duel
+    }
+}
+
+impl Deck {
+    pub fn draw(&mut self, game: &Game) -> Challenge {
+        // Team challenges need more than one player per team
+        let teams = game.for_ai.as_ref().map_or(0, |side| side.nb_players) > 1
+            && game.against_ai.as_ref().map_or(0, |side| side.nb_players) > 1;
+        let allowed: Vec<(&ChallengeInfo, Category)> = playable(&game.rules.venue, teams);
+
+        let last = self.draws.last().copied();
+        let available = |deck: &Deck, bans: bool| -> Vec<(&ChallengeInfo, Category)> {
+            allowed
+                .iter()
+                .filter(|(info, _)| !(bans && deck.banned.contains(&info.key)))
+                .filter(|(info, _)| !deck.draws[deck.cycle_start..].contains(&info.key))
+                .filter(|(info, _)| Some(info.key) != last)
+                .copied()
+                .collect()
+        };
+        // Reshuffle once the deck is exhausted, bans are lifted if everything is banned
+        let mut candidates = available(self, true);
+        if candidates.is_empty() {
+            self.cycle_start = self.draws.len();
+            candidates = available(self, true);
+        }
+        if candidates.is_empty() {
+            candidates = available(self, false);
+        }
+        if candidates.is_empty() {
+            candidates = allowed;
+        }
+
+        let weights: Vec<f64> = candidates
+            .iter()
+            .map(|(info, category)| {
+                // Both categories are as likely whatever their size
+                let in_category = candidates.iter().filter(|(_, c)| c == category).count();
+                let mut weight = 1.0 / in_category as f64;
+                if last.map(category_of) == Some(*category) {
+                    weight *= SAME_CATEGORY_WEIGHT;
+                }
+                if let Some(drawn) = self.draws.iter().rposition(|key| *key == info.key) {
+                    let age = self.draws.len() - drawn;
+                    weight *= age.min(RECENCY_WINDOW) as f64 / RECENCY_WINDOW as f64;
+                }
+                weight
+            })
+            .collect();
+
+        let (info, _) = match WeightedIndex::new(&weights) {
+            Ok(index) => candidates[index.sample(&mut rand::thread_rng())],
+            Err(_) => *candidates.choose(&mut rand::thread_rng()).unwrap(),
+        };
+        self.draws.push(info.key);
+        Challenge::new(info)
+    }
+
+    pub fn ban(&mut self, key: &'static str) {
+        if !self.banned.contains(&key) {
+            self.banned.push(key);
+        }
+    }
+
+    pub fn record(
+        &mut self,
+        challenge: &Challenge,
+        outcome: Option<ChallengeOutcome>,
+        game: &Game,
+    ) {
+        self.history.push(Played {
+            text: challenge.text.clone(),
+            outcome,
+            team_names: game.team_names.clone(),
+        });
+    }
+}
+
+// "{a|b|c}" is replaced by one of the options, "{@list}" by one of the words of
+// the "words.list" catalog entry (options separated by '|' as well)
+pub fn fill_in<R: Rng>(template: &str, rng: &mut R) -> String {
+    let mut text = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        text += &rest[..start];
+        let options = match &rest[start + 1..end] {
+            list if list.starts_with('@') => {
+                i18n::try_lookup(&format!("words.{}", &list[1..])).unwrap_or(list)
+            }
+            options => options,
+        };
+        let options: Vec<&str> = options.split('|').collect();
+        text += options.choose(rng).unwrap_or(&"");
+        rest = &rest[end + 1..];
+    }
+    text + rest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::data::score::Source;
+
+    fn venue(packs: &[Category]) -> Venue {
+        let mut venue = Venue::new();
+        venue.packs = packs.to_vec();
+        venue
+    }
+
+    #[test]
+    fn only_the_chosen_packs_are_drawn() {
+        let teams = playable(&venue(&[Category::Team]), true);
+        assert!(!teams.is_empty());
+        assert!(teams
+            .iter()
+            .all(|(_, category)| *category == Category::Team));
+
+        let duels = playable(&venue(&[Category::Duel]), true);
+        assert!(duels
+            .iter()
+            .all(|(_, category)| *category == Category::Duel));
+        assert_eq!(
+            playable(&venue(CATEGORIES), true).len(),
+            teams.len() + duels.len()
+        );
+    }
+
+    #[test]
+    fn duels_are_drawn_when_the_packs_leave_nothing() {
+        let allowed = playable(&venue(&[Category::Team]), false);
+        assert!(!allowed.is_empty());
+        assert!(allowed
+            .iter()
+            .all(|(_, category)| *category == Category::Duel));
+    }
+
+    fn game(players: u8) -> Game {
+        let mut game = Game::new();
+        game.for_ai = Some(Side::new(players));
+        game.against_ai = Some(Side::new(players));
+        game.rules.venue = venue(CATEGORIES);
+        game
+    }
+
+    #[test]
+    fn every_challenge_is_drawn_once_before_any_repeat() {
+        let game = game(2);
+        let size = playable(&game.rules.venue, true).len();
+        let mut deck = Deck::default();
+        let mut keys: Vec<&str> = (0..size).map(|_| deck.draw(&game).key).collect();
+        keys.sort();
+        keys.dedup();
+        assert_eq!(keys.len(), size);
+
+        let last = deck.draws.last().copied();
+        assert!(Some(deck.draw(&game).key) != last);
+    }
+
+    #[test]
+    fn banned_challenges_are_left_out() {
+        let game = game(1);
+        let keys: Vec<&'static str> = playable(&game.rules.venue, false)
+            .iter()
+            .map(|(info, _)| info.key)
+            .collect();
+        let mut deck = Deck::default();
+        for key in &keys[1..] {
+            deck.ban(key);
+        }
+        assert_eq!(deck.draw(&game).key, keys[0]);
+    }
+
+    #[test]
+    fn votes_follow_the_majority_then_the_tie_rule() {
+        let mut game = game(3);
+        let mut vote = Vote::new(3);
+        vote.cast(Some(AiSide::Against));
+        vote.cast(Some(AiSide::For));
+        vote.cast(Some(AiSide::Against));
+        vote.cast(Some(AiSide::For));
+        assert!(vote.is_complete());
+        assert_eq!(vote.cast_count(), 3);
+        assert!(vote.verdict(TieRule::Draw, &game) == Some(ChallengeOutcome::Won(AiSide::Against)));
+
+        let mut tie = Vote::new(3);
+        tie.cast(Some(AiSide::For));
+        tie.cast(Some(AiSide::Against));
+        tie.cast(None);
+        assert!(tie.verdict(TieRule::Draw, &game) == Some(ChallengeOutcome::Draw));
+        assert!(tie.verdict(TieRule::Referee, &game).is_none());
+        assert!(tie.verdict(TieRule::Underdog, &game) == Some(ChallengeOutcome::Draw));
+        game.score(&AiSide::For, Source::Captures, 5);
+        assert!(
+            tie.verdict(TieRule::Underdog, &game) == Some(ChallengeOutcome::Won(AiSide::Against))
+        );
+    }
+
+    #[test]
+    fn rewards_split_the_points() {
+        let mut challenge = Challenge::new(&SOLO_CHALLENGES[0]);
+        challenge.points = 7;
+        assert_eq!(
+            challenge.rewards(&ChallengeOutcome::Won(AiSide::For)),
+            (7, 0)
+        );
+        assert_eq!(
+            challenge.rewards(&ChallengeOutcome::Won(AiSide::Against)),
+            (0, 7)
+        );
+        assert_eq!(challenge.rewards(&ChallengeOutcome::Draw), (3, 3));
+        assert_eq!(challenge.rewards(&ChallengeOutcome::BothFailed), (0, 0));
+    }
+
+    #[test]
+    fn templates_are_filled_in() {
+        let text = fill_in("Name {a|b} and {c}", &mut rand::thread_rng());
+        assert!(text == "Name a and c" || text == "Name b and c");
+    }
+}
@@ -0,0 +1,195 @@
+use super::{
+    game::{AiSide, Game, Position, PositionError, DATABASE_POINTS},
+    score::Source,
+    timeline::Moment,
+};
+
+pub const DEFAULT_DATABASE_SUPPLY: u32 = 3;
+pub const MAX_DATABASE_SUPPLY: u32 = 6;
+
+// What a team can do to a database of the other team
+#[derive(Clone, Copy, PartialEq)]
+pub enum Raid {
+    Off,
+    // The database and its points change hands
+    Steal,
+    // The database is removed and its owner loses its points
+    Destroy,
+}
+
+impl Raid {
+    pub fn next(&self) -> Raid {
+        match self {
+            Raid::Off => Raid::Steal,
+            Raid::Steal => Raid::Destroy,
+            Raid::Destroy => Raid::Off,
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Raid> {
+        match code {
+            "off" => Some(Raid::Off),
+            "steal" => Some(Raid::Steal),
+            "destroy" => Some(Raid::Destroy),
+            _ => None,
+        }
+    }
+}
+
+pub struct Database {
+    // Team that placed it, its supply is not given back
+    pub placed_by: AiSide,
+    pub owner: AiSide,
+    // Where it stands on the board, when the referee entered it
+    pub position: Option<Position>,
+    // Destroyed by a raid, kept so that it still counts against its team's supply
+    pub destroyed: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DatabaseError {
+    Position(PositionError),
+    // The team placed every database of its supply
+    NoSupply,
+    // Already a database there that cannot be raided
+    Taken,
+}
+
+impl From<PositionError> for DatabaseError {
+    fn from(err: PositionError) -> DatabaseError {
+        DatabaseError::Position(err)
+    }
+}
+
+impl Game {
+    pub fn supply_left(&self, ai_side: &AiSide) -> u32 {
+        let placed = self
+            .databases
+            .iter()
+            .filter(|database| database.placed_by == *ai_side)
+            .count() as u32;
+        self.rules.database_supply.saturating_sub(placed)
+    }
+
+    // Places a database of ai_side, or raids the other team's database standing there
+    pub fn place_database(
+        &mut self,
+        ai_side: AiSide,
+        position: Option<Position>,
+    ) -> Result<(), DatabaseError> {
+        let found = position.and_then(|position| {
+            self.databases
+                .iter()
+                .position(|database| !database.destroyed && database.position == Some(position))
+        });
+        if let Some(index) = found {
+            if self.databases[index].owner == ai_side || self.rules.raid == Raid::Off {
+                return Err(DatabaseError::Taken);
+            }
+            self.raid_database(index, ai_side.clone());
+            self.mark(Moment::Database(ai_side));
+            return Ok(());
+        }
+        if self.supply_left(&ai_side) == 0 {
+            return Err(DatabaseError::NoSupply);
+        }
+        self.score(&ai_side, Source::Databases, DATABASE_POINTS as i64);
+        self.databases.push(Database {
+            placed_by: ai_side.clone(),
+            owner: ai_side.clone(),
+            position,
+            destroyed: false,
+        });
+        self.mark(Moment::Database(ai_side));
+        Ok(())
+    }
+
+    fn raid_database(&mut self, index: usize, raider: AiSide) {
+        let victim = self.databases[index].owner.clone();
+        self.score(&victim, Source::Databases, -(DATABASE_POINTS as i64));
+        match self.rules.raid {
+            Raid::Steal => {
+                self.databases[index].owner = raider.clone();
+                self.score(&raider, Source::Databases, DATABASE_POINTS as i64);
+            }
+            _ => self.databases[index].destroyed = true,
+        }
+    }
+
+    // Data leak event: the victim's latest database changes hands with its points,
+    // false when the victim has none to steal
+    pub fn leak_database(&mut self, thief: &AiSide) -> bool {
+        let victim = thief.switch();
+        match self
+            .databases
+            .iter_mut()
+            .rev()
+            .find(|database| !database.destroyed && database.owner == victim)
+        {
+            Some(database) => database.owner = thief.clone(),
+            None => return false,
+        }
+        self.score(&victim, Source::Events, -(DATABASE_POINTS as i64));
+        self.score(thief, Source::Events, DATABASE_POINTS as i64);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::data::side::Side;
+
+    fn game(raid: Raid) -> Game {
+        let mut game = Game::new();
+        game.for_ai = Some(Side::new(2));
+        game.against_ai = Some(Side::new(2));
+        game.rules.database_supply = 1;
+        game.rules.raid = raid;
+        game
+    }
+
+    #[test]
+    fn destroyed_database_keeps_using_the_supply() {
+        let mut game = game(Raid::Destroy);
+        let position = "C4".parse().ok();
+        game.place_database(AiSide::For, position).unwrap();
+        game.place_database(AiSide::Against, position).unwrap();
+
+        assert_eq!(game.supply_left(&AiSide::For), 0);
+        assert_eq!(
+            game.place_database(AiSide::For, "D4".parse().ok()),
+            Err(DatabaseError::NoSupply)
+        );
+        assert_eq!(game.points_from(&AiSide::For, Source::Databases), 0);
+        assert_eq!(game.points_from(&AiSide::Against, Source::Databases), 0);
+        assert!(game.databases[0].destroyed);
+    }
+
+    #[test]
+    fn destroyed_database_frees_its_cell() {
+        let mut game = game(Raid::Destroy);
+        let position = "C4".parse().ok();
+        game.place_database(AiSide::For, position).unwrap();
+        game.place_database(AiSide::Against, position).unwrap();
+
+        game.place_database(AiSide::Against, position).unwrap();
+        assert_eq!(game.supply_left(&AiSide::Against), 0);
+        assert!(!game.leak_database(&AiSide::Against));
+    }
+
+    #[test]
+    fn stolen_database_changes_owner_and_points() {
+        let mut game = game(Raid::Steal);
+        let position = "C4".parse().ok();
+        game.place_database(AiSide::For, position).unwrap();
+        game.place_database(AiSide::Against, position).unwrap();
+
+        assert!(game.databases[0].owner == AiSide::Against);
+        assert_eq!(game.points_from(&AiSide::For, Source::Databases), 0);
+        assert_eq!(
+            game.points_from(&AiSide::Against, Source::Databases),
+            DATABASE_POINTS as i64
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    capture::Location,
+    game::{AiSide, Game, Position},
+    side::Side,
+};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EventRate {
+    Off,
+    Rare,
+    Normal,
+    Frequent,
+}
+
+impl EventRate {
+    // Chance to draw an event at each end of turn
+    pub fn prob(&self) -> f64 {
+        match self {
+            EventRate::Off => 0.0,
+            EventRate::Rare => 0.1,
+            EventRate::Normal => 0.2,
+            EventRate::Frequent => 0.35,
+        }
+    }
+
+    pub fn next(&self) -> EventRate {
+        match self {
+            EventRate::Off => EventRate::Rare,
+            EventRate::Rare => EventRate::Normal,
+            EventRate::Normal => EventRate::Frequent,
+            EventRate::Frequent => EventRate::Off,
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<EventRate> {
+        match code {
+            "off" => Some(EventRate::Off),
+            "rare" => Some(EventRate::Rare),
+            "normal" => Some(EventRate::Normal),
+            "frequent" => Some(EventRate::Frequent),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum EventKind {
+    // The capture point neither scores nor changes
+    Firewall(Location),
+    // Scores are hidden
+    Blackout,
+    // Captures score double
+    Overclock,
+    // The team has to move a player onto its VPN
+    Teleport(AiSide, Option<Position>),
+    // The team's VPN jumps to another row
+    VpnMove(AiSide, Position),
+    // The trailing team takes a database from the leading one
+    DataLeak(AiSide),
+}
+
+pub struct LucyEvent {
+    pub kind: EventKind,
+    turns_left: u32,
+}
+
+impl LucyEvent {
+    pub fn rounds_left(&self) -> u32 {
+        self.turns_left.div_ceil(2)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Card {
+    Firewall,
+    Blackout,
+    Overclock,
+    Teleport,
+    VpnMove,
+    DataLeak,
+}
+
+// Every card of the deck and how many rounds it lasts
+const CARDS: &[(Card, u32)] = &[
+    (Card::Firewall, 2),
+    (Card::Blackout, 2),
+    (Card::Overclock, 1),
+    (Card::Teleport, 1),
+    (Card::VpnMove, 1),
+    (Card::DataLeak, 1),
+];
+
+// Shuffled again once every card was drawn
+#[derive(Default)]
+pub struct EventDeck(Vec<(Card, u32)>);
+
+impl EventDeck {
+    fn draw<R: Rng>(&mut self, rng: &mut R) -> (Card, u32) {
+        if self.0.is_empty() {
+            self.0 = CARDS.to_vec();
+            self.0.shuffle(rng);
+        }
+        self.0.pop().unwrap_or((Card::Blackout, 1))
+    }
+}
+
+impl Game {
+    pub fn trigger_event(&mut self) {
+        let mut rng = rand::thread_rng();
+        let (card, rounds) = self.events.draw(&mut rng);
+        let kind = match card {
+            Card::Firewall => EventKind::Firewall(if rng.gen_bool(1.0 / 3.0) {
+                Location::Center
+            } else {
+                Location::Home(rng.gen())
+            }),
+            Card::Blackout => EventKind::Blackout,
+            Card::Overclock => EventKind::Overclock,
+            Card::Teleport => {
+                let ai_side: AiSide = rng.gen();
+                let vpn = self.vpn(&ai_side).map(|vpn| vpn.position);
+                EventKind::Teleport(ai_side, vpn)
+            }
+            Card::VpnMove => {
+                let ai_side: AiSide = rng.gen();
+                match self.relocate_vpn(&ai_side) {
+                    Some(vpn) => EventKind::VpnMove(ai_side, vpn),
+                    None => EventKind::Blackout,
+                }
+            }
+            Card::DataLeak => {
+                let progress = |side: &Option<Side>| side.as_ref().map_or(0, Side::progress);
+                let thief = match progress(&self.for_ai).cmp(&progress(&self.against_ai)) {
+                    Ordering::Less => AiSide::For,
+                    Ordering::Greater => AiSide::Against,
+                    Ordering::Equal => rng.gen(),
+                };
+                if self.leak_database(&thief) {
+                    EventKind::DataLeak(thief)
+                } else {
+                    EventKind::Blackout
+                }
+            }
+        };
+        self.event = Some(LucyEvent {
+            kind,
+            turns_left: rounds * 2,
+        });
+    }
+
+    // Called at each end of turn
+    pub fn tick_event(&mut self) {
+        if let Some(event) = self.event.as_mut() {
+            event.turns_left = event.turns_left.saturating_sub(1);
+            if event.turns_left == 0 {
+                self.event = None;
+            }
+        }
+    }
+
+    pub fn is_frozen(&self, capture: &Location) -> bool {
+        matches!(&self.event, Some(LucyEvent { kind: EventKind::Firewall(frozen), .. }) if frozen == capture)
+    }
+
+    pub fn is_blackout(&self) -> bool {
+        matches!(
+            self.event,
+            Some(LucyEvent {
+                kind: EventKind::Blackout,
+                ..
+            })
+        )
+    }
+
+    pub fn capture_multiplier(&self) -> u32 {
+        match self.event {
+            Some(LucyEvent {
+                kind: EventKind::Overclock,
+                ..
+            }) => 2,
+            _ => 1,
+        }
+    }
+}
@@ -1,15 +1,35 @@
-use std::ops::RangeInclusive;
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::RangeInclusive,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
-use rand::{distributions::Standard, prelude::Distribution, Rng};
+use rand::{distributions::Standard, prelude::Distribution, rngs::StdRng, Rng, SeedableRng};
 
-use super::{capture::Capture, race::Race, side::Side};
+use super::{
+    capture::{CapturePoint, Location},
+    database::Database,
+    event::{EventDeck, LucyEvent},
+    handicap::Handicap,
+    race::{Race, RaceResult},
+    rules::{GameMode, Rules},
+    score::{Score, Source},
+    side::Side,
+    timeline::{Moment, Snapshot},
+    vpn::{Vpn, VpnUse},
+};
 
 pub const ROW_RANGE: RangeInclusive<u8> = 1..=7;
 pub const COL_RANGE: RangeInclusive<u8> = 1..=11;
 
 pub const DATABASE_POINTS: u32 = 7;
 
-const COL_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+// Longest typed position, "C4"
+pub const MAX_POSITION_LEN: usize = 2;
+
+pub const COL_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
 // Every end of turn test this
 pub const RACE_PROB: f64 = 1.0 / 5.0;
@@ -45,23 +65,74 @@ impl Distribution<AiSide> for Standard {
     }
 }
 
+pub enum Outcome {
+    Won(AiSide),
+    Level,
+}
+
 pub struct Game {
     pub for_ai: Option<Side>,
     pub against_ai: Option<Side>,
-    pub center_capture: Option<Capture>,
-    pub race: Option<Race>,
+    // Capture points in board order: For AI home, center, Against AI home
+    pub captures: Vec<CapturePoint>,
+    // Running races, oldest first
+    pub races: Vec<Race>,
+    pub race_history: Vec<RaceResult>,
+    // Each team's VPN once generated, and every teleport so far
+    pub vpns: Vec<Vpn>,
+    pub vpn_log: Vec<VpnUse>,
+    // Placed databases, oldest first
+    pub databases: Vec<Database>,
+    // Every change of progress, by source
+    pub scores: Vec<Score>,
+    // Progress at every end of turn, and what moved it during the current turn
+    pub timeline: Vec<Snapshot>,
+    pub(super) moments: Vec<Moment>,
+    // Lucy's game-wide modifier, at most one at a time
+    pub event: Option<LucyEvent>,
+    pub(super) events: EventDeck,
     pub turn: AiSide,
+    pub rules: Rules,
+    pub start: Option<Instant>,
+    pub finished: Option<Instant>,
+    pub sudden_death: bool,
+    // Names of the (For AI, Against AI) teams in a tournament match
+    pub team_names: Option<(String, String)>,
+    // Seed of the board draws, the same seed and keys give the same boards
+    pub seed: u64,
+    pub board_rng: StdRng,
 }
 
 impl Game {
     pub fn new() -> Game {
         let mut rng = rand::thread_rng();
+        let seed = rng.gen();
         Game {
             for_ai: None,
             against_ai: None,
-            center_capture: None,
-            race: None,
+            captures: vec![
+                CapturePoint::new(Location::Home(AiSide::For)),
+                CapturePoint::new(Location::Center),
+                CapturePoint::new(Location::Home(AiSide::Against)),
+            ],
+            races: vec![],
+            race_history: vec![],
+            vpns: vec![],
+            vpn_log: vec![],
+            databases: vec![],
+            scores: vec![],
+            timeline: vec![],
+            moments: vec![],
+            event: None,
+            events: EventDeck::default(),
             turn: rng.gen(),
+            rules: Rules::new(),
+            start: None,
+            finished: None,
+            sudden_death: false,
+            team_names: None,
+            seed,
+            board_rng: StdRng::seed_from_u64(seed),
         }
     }
 
@@ -71,9 +142,157 @@ impl Game {
             AiSide::Against => self.against_ai.as_mut(),
         }
     }
+
+    pub fn team_name(&self, ai_side: &AiSide) -> Option<&str> {
+        let (for_ai, against_ai) = self.team_names.as_ref()?;
+        Some(match ai_side {
+            AiSide::For => for_ai,
+            AiSide::Against => against_ai,
+        })
+    }
+
+    pub fn side_mut(&mut self, ai_side: &AiSide) -> Option<&mut Side> {
+        match ai_side {
+            AiSide::For => self.for_ai.as_mut(),
+            AiSide::Against => self.against_ai.as_mut(),
+        }
+    }
+
+    pub fn claim_race(&mut self, index: usize, ai_side: AiSide) {
+        if index >= self.races.len() {
+            return;
+        }
+        let result = self.races.remove(index).claim(ai_side.clone());
+        self.score(&ai_side, Source::Races, result.points as i64);
+        self.race_history.push(result);
+        self.mark(Moment::Race(Some(ai_side)));
+    }
+
+    // Races nobody claimed in time cost both teams
+    pub fn expire_races(&mut self) {
+        let (finished, running): (Vec<Race>, Vec<Race>) =
+            self.races.drain(..).partition(Race::is_finished);
+        self.races = running;
+        for race in finished {
+            let result = race.miss();
+            for ai_side in [AiSide::For, AiSide::Against] {
+                self.score(&ai_side, Source::Penalties, -(result.points as i64));
+            }
+            self.race_history.push(result);
+            self.mark(Moment::Race(None));
+        }
+    }
+
+    // Players left to judge a duel
+    pub fn judges(&self) -> u32 {
+        let players = |side: &Option<Side>| side.as_ref().map_or(0, |side| side.nb_players as u32);
+        (players(&self.for_ai) + players(&self.against_ai)).saturating_sub(2)
+    }
+
+    pub fn suggest_handicaps(&mut self) {
+        if let (Some(for_ai), Some(against_ai)) = (self.for_ai.as_mut(), self.against_ai.as_mut()) {
+            for_ai.handicap = Handicap::suggest(for_ai.nb_players, against_ai.nb_players);
+            against_ai.handicap = Handicap::suggest(against_ai.nb_players, for_ai.nb_players);
+        }
+    }
+
+    pub fn begin(&mut self) {
+        for ai_side in [AiSide::For, AiSide::Against] {
+            let (progress, captures) = match self.side_mut(&ai_side) {
+                Some(side) => (side.handicap.progress, side.handicap.captures),
+                None => continue,
+            };
+            self.score(&ai_side, Source::Handicap, progress as i64);
+            // Handicap captures start on the team's own capture point
+            for _ in 0..captures {
+                self.add_capture(&Location::Home(ai_side.clone()), ai_side.clone());
+            }
+        }
+        self.start = Some(Instant::now());
+        self.snapshot();
+    }
+
+    // Change turn (SWITCH TEAM), the team now playing scores its capture points
+    pub fn end_turn(&mut self) {
+        let mut rng = rand::thread_rng();
+        self.snapshot();
+        let ended = self.turn.clone();
+        self.end_vpn_turn(&ended);
+        if let Some(ref mut side) = self.get_turn() {
+            side.nb_rounds += 1;
+        }
+        self.turn = self.turn.switch();
+
+        let turn = self.turn.clone();
+        let (homes, center) = self.capture_points(&turn);
+        let multiplier = self.get_turn().map_or(1.0, |side| side.handicap.multiplier);
+        let total = ((homes + center) as f32 * multiplier).round() as i64;
+        let center = ((center as f32 * multiplier).round() as i64).min(total);
+        self.score(&turn, Source::Center, center);
+        self.score(&turn, Source::Captures, total - center);
+
+        self.tick_event();
+        if self.event.is_none() && rng.gen_bool(self.rules.event_rate.prob()) {
+            self.trigger_event();
+        }
+
+        if self.races.len() < self.rules.max_races && rng.gen_bool(RACE_PROB) {
+            let taken: Vec<Position> = self.races.iter().map(|race| race.position).collect();
+            self.races.push(Race::new(
+                self.vpn_positions().as_ref(),
+                self.rules.race_balance,
+                &taken,
+            ));
+        }
+    }
+
+    // Races still running are dropped, nobody can claim or miss them anymore
+    pub fn finish(&mut self) {
+        self.races.clear();
+        self.finished = Some(Instant::now());
+        self.snapshot();
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        Some(self.finished?.duration_since(self.start?))
+    }
+
+    pub fn remaining_time(&self) -> Option<chrono::Duration> {
+        let limit = self.rules.time_limit()?;
+        let took = self.start?.elapsed();
+        if limit <= took {
+            return Some(chrono::Duration::zero());
+        }
+        Some(chrono::Duration::from_std(limit - took).unwrap())
+    }
+
+    pub fn outcome(&self) -> Option<Outcome> {
+        let for_ai = self.for_ai.as_ref()?;
+        let against_ai = self.against_ai.as_ref()?;
+
+        let over = match self.rules.mode {
+            // Teams crossing it in the same tally are settled by how far past it they went
+            GameMode::Target(target) => {
+                for_ai.progress() >= target || against_ai.progress() >= target
+            }
+            GameMode::Rounds(rounds) => {
+                for_ai.nb_rounds >= rounds && against_ai.nb_rounds >= rounds
+            }
+            GameMode::Timed(_) => self.remaining_time().is_some_and(|r| r.is_zero()),
+        };
+
+        if !over {
+            return None;
+        }
+        Some(match for_ai.progress().cmp(&against_ai.progress()) {
+            Ordering::Greater => Outcome::Won(AiSide::For),
+            Ordering::Less => Outcome::Won(AiSide::Against),
+            Ordering::Equal => Outcome::Level,
+        })
+    }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq)]
 pub struct Position {
     pub(super) row: u8,
     pub(super) col: u8,
@@ -88,12 +307,123 @@ impl Distribution<Position> for Standard {
     }
 }
 
-impl ToString for Position {
-    fn to_string(&self) -> String {
-        format!(
+impl Position {
+    // Neighbouring cell, None when off the board
+    pub fn shifted(&self, rows: i8, cols: i8) -> Option<Position> {
+        let position = Position {
+            row: self.row.checked_add_signed(rows)?,
+            col: self.col.checked_add_signed(cols)?,
+        };
+        (ROW_RANGE.contains(&position.row) && COL_RANGE.contains(&position.col)).then_some(position)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PositionError {
+    // Not a column letter followed by a row number
+    Format,
+    OffBoard,
+}
+
+// Why a position cannot hold a setup item
+#[derive(Debug, PartialEq)]
+pub enum PlacementError {
+    Position(PositionError),
+    // A base or the middle of the board
+    Illegal,
+    // Outside the team's half, or too close to the middle
+    WrongHalf,
+    // VPNs stay on their team's column
+    WrongColumn,
+    // Another feature already stands there
+    Taken,
+    // No position meets the board constraints
+    NoRoom,
+    // Not a setup code
+    Code,
+}
+
+impl From<PositionError> for PlacementError {
+    fn from(err: PositionError) -> PlacementError {
+        PlacementError::Position(err)
+    }
+}
+
+// Reads board positions the way they are displayed, "C4" or "c4"
+impl FromStr for Position {
+    type Err = PositionError;
+
+    fn from_str(s: &str) -> Result<Position, PositionError> {
+        let s = s.trim();
+        let letter = s.chars().next().ok_or(PositionError::Format)?;
+        let col = COL_ALPHABET
+            .find(letter.to_ascii_uppercase())
+            .ok_or(PositionError::Format)?;
+        let row: u8 = s[letter.len_utf8()..]
+            .parse()
+            .map_err(|_| PositionError::Format)?;
+        let position = Position {
+            row,
+            col: col as u8 + 1,
+        };
+        if !ROW_RANGE.contains(&position.row) || !COL_RANGE.contains(&position.col) {
+            return Err(PositionError::OffBoard);
+        }
+        Ok(position)
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "{}{}",
             COL_ALPHABET.chars().nth(self.col as usize - 1).unwrap(),
-            self.row.to_string()
+            self.row
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::data::side::TOTAL_PROGRESS;
+
+    fn game(mode: GameMode) -> Game {
+        let mut game = Game::new();
+        game.for_ai = Some(Side::new(2));
+        game.against_ai = Some(Side::new(2));
+        game.rules.mode = mode;
+        game
+    }
+
+    #[test]
+    fn progress_is_only_capped_in_target_games() {
+        let mut rounds = game(GameMode::Rounds(10));
+        rounds.score(&AiSide::For, Source::Races, 150);
+        assert_eq!(rounds.for_ai.as_ref().unwrap().progress(), 150);
+
+        let mut target = game(GameMode::Target(80));
+        target.score(&AiSide::For, Source::Races, 150);
+        assert_eq!(target.for_ai.as_ref().unwrap().progress(), TOTAL_PROGRESS);
+    }
+
+    #[test]
+    fn simultaneous_crossing_goes_to_the_furthest_team() {
+        let mut game = game(GameMode::Target(50));
+        game.score(&AiSide::For, Source::Challenges, 52);
+        game.score(&AiSide::Against, Source::Challenges, 55);
+        assert!(matches!(
+            game.outcome(),
+            Some(Outcome::Won(AiSide::Against))
+        ));
+    }
+
+    #[test]
+    fn simultaneous_crossing_by_as_much_is_level() {
+        let mut game = game(GameMode::Target(50));
+        game.score(&AiSide::For, Source::Challenges, 55);
+        game.score(&AiSide::Against, Source::Challenges, 55);
+        assert!(matches!(game.outcome(), Some(Outcome::Level)));
+    }
+}
@@ -0,0 +1,80 @@
+use super::game::AiSide;
+
+const PROGRESS_STEP: u32 = 5;
+pub const MAX_PROGRESS: u32 = 30;
+pub const MAX_CAPTURES: u32 = 3;
+const MULTIPLIER_STEP: f32 = 0.25;
+pub const MAX_MULTIPLIER: f32 = 2.0;
+
+// Suggested bonus for each missing player
+const SUGGESTED_PROGRESS: u32 = 10;
+const SUGGESTED_CAPTURES: u32 = 1;
+const SUGGESTED_MULTIPLIER: f32 = 0.25;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum HandicapField {
+    Progress,
+    Captures,
+    Multiplier,
+}
+
+pub const HANDICAP_FIELDS: &[(AiSide, HandicapField)] = &[
+    (AiSide::For, HandicapField::Progress),
+    (AiSide::For, HandicapField::Captures),
+    (AiSide::For, HandicapField::Multiplier),
+    (AiSide::Against, HandicapField::Progress),
+    (AiSide::Against, HandicapField::Captures),
+    (AiSide::Against, HandicapField::Multiplier),
+];
+
+#[derive(Clone, PartialEq)]
+pub struct Handicap {
+    // Progress given at the start of the game
+    pub progress: u32,
+    // Captures already held at the start of the game
+    pub captures: u32,
+    // Applied to the capture points gained at the end of each turn
+    pub multiplier: f32,
+}
+
+impl Handicap {
+    pub fn none() -> Handicap {
+        Handicap {
+            progress: 0,
+            captures: 0,
+            multiplier: 1.0,
+        }
+    }
+
+    // The smaller team gets a bonus for each player it lacks
+    pub fn suggest(nb_players: u8, other_nb_players: u8) -> Handicap {
+        let missing = other_nb_players.saturating_sub(nb_players) as u32;
+        Handicap {
+            progress: (missing * SUGGESTED_PROGRESS).min(MAX_PROGRESS),
+            captures: (missing * SUGGESTED_CAPTURES).min(MAX_CAPTURES),
+            multiplier: (1.0 + missing as f32 * SUGGESTED_MULTIPLIER).min(MAX_MULTIPLIER),
+        }
+    }
+
+    pub fn increase(&mut self, field: HandicapField) {
+        match field {
+            HandicapField::Progress => {
+                self.progress = (self.progress + PROGRESS_STEP).min(MAX_PROGRESS)
+            }
+            HandicapField::Captures => self.captures = (self.captures + 1).min(MAX_CAPTURES),
+            HandicapField::Multiplier => {
+                self.multiplier = (self.multiplier + MULTIPLIER_STEP).min(MAX_MULTIPLIER)
+            }
+        }
+    }
+
+    pub fn decrease(&mut self, field: HandicapField) {
+        match field {
+            HandicapField::Progress => self.progress = self.progress.saturating_sub(PROGRESS_STEP),
+            HandicapField::Captures => self.captures = self.captures.saturating_sub(1),
+            HandicapField::Multiplier => {
+                self.multiplier = (self.multiplier - MULTIPLIER_STEP).max(1.0)
+            }
+        }
+    }
+}
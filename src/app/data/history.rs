@@ -0,0 +1,564 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    mem,
+    time::Duration,
+};
+
+use chrono::Local;
+use itertools::Itertools;
+
+use super::{
+    game::{AiSide, Game, Outcome},
+    rules::GameMode,
+    score::{Score, Source, SOURCES},
+    timeline::{deepest_deficit, Moment, Snapshot},
+};
+
+pub const HISTORY_FILE: &str = "cyberconnect-history.txt";
+// Players listed in the stats
+const TOP_PLAYERS: usize = 5;
+
+// One team of an archived game
+pub struct Team {
+    // Tournament team name
+    pub name: Option<String>,
+    pub nb_players: u8,
+    // Names typed during setup, empty when left out
+    pub players: Vec<String>,
+    pub progress: u32,
+    pub nb_rounds: u32,
+}
+
+impl Team {
+    fn new(game: &Game, ai_side: &AiSide) -> Team {
+        let side = match ai_side {
+            AiSide::For => game.for_ai.as_ref(),
+            AiSide::Against => game.against_ai.as_ref(),
+        };
+        Team {
+            name: game.team_name(ai_side).map(str::to_string),
+            nb_players: side.map_or(0, |side| side.nb_players),
+            players: side.map_or(vec![], |side| {
+                side.players.iter().map(|p| p.trim().to_string()).collect()
+            }),
+            progress: side.map_or(0, |side| side.progress()),
+            nb_rounds: side.map_or(0, |side| side.nb_rounds),
+        }
+    }
+
+    fn empty() -> Team {
+        Team {
+            name: None,
+            nb_players: 0,
+            players: vec![],
+            progress: 0,
+            nb_rounds: 0,
+        }
+    }
+
+    fn named(&self) -> impl Iterator<Item = &String> {
+        self.name
+            .iter()
+            .chain(&self.players)
+            .filter(|name| !name.is_empty())
+    }
+}
+
+// A finished game, with what is needed to look back at it
+pub struct Record {
+    pub id: u32,
+    // Local date and time it ended
+    pub played: String,
+    pub duration: Duration,
+    pub winner: Option<AiSide>,
+    pub for_ai: Team,
+    pub against_ai: Team,
+    pub mode: GameMode,
+    // Rules as listed on the setup sheet, in the language the game was played in
+    pub rules: Vec<String>,
+    // Setup code of the board, the layout can be generated again from it
+    pub code: Option<String>,
+    // Seed of the board draws, None for games archived before it was kept
+    pub seed: Option<u64>,
+    pub timeline: Vec<Snapshot>,
+    pub scores: Vec<Score>,
+}
+
+impl Record {
+    // The id is given once the record is added to the history
+    pub fn new(
+        game: &Game,
+        winner: Option<AiSide>,
+        code: Option<String>,
+        rules: Vec<String>,
+    ) -> Record {
+        Record {
+            id: 0,
+            played: Local::now().format("%Y-%m-%d %H:%M").to_string(),
+            duration: game.duration().unwrap_or_default(),
+            winner,
+            for_ai: Team::new(game, &AiSide::For),
+            against_ai: Team::new(game, &AiSide::Against),
+            mode: game.rules.mode.clone(),
+            rules,
+            code,
+            seed: Some(game.seed),
+            timeline: game.timeline.clone(),
+            scores: game.scores.clone(),
+        }
+    }
+
+    pub fn team(&self, ai_side: &AiSide) -> &Team {
+        match ai_side {
+            AiSide::For => &self.for_ai,
+            AiSide::Against => &self.against_ai,
+        }
+    }
+
+    pub fn turns(&self) -> usize {
+        self.timeline.len().saturating_sub(1)
+    }
+
+    pub fn points_from(&self, ai_side: &AiSide, source: Source) -> i64 {
+        self.scores
+            .iter()
+            .filter(|score| score.ai_side == *ai_side && score.source == source)
+            .map(|score| score.points)
+            .sum()
+    }
+
+    // Points the winner was behind at worst
+    pub fn comeback(&self) -> u32 {
+        self.winner
+            .as_ref()
+            .map_or(0, |winner| deepest_deficit(&self.timeline, winner))
+    }
+}
+
+#[derive(Default)]
+pub struct Filter {
+    // Part of a team or player name, in any case
+    pub name: String,
+    pub outcome: Option<Outcome>,
+    // Only the kind of mode is compared
+    pub mode: Option<GameMode>,
+}
+
+impl Filter {
+    pub fn matches(&self, record: &Record) -> bool {
+        let name = self.name.trim().to_lowercase();
+        let named = name.is_empty()
+            || record
+                .for_ai
+                .named()
+                .chain(record.against_ai.named())
+                .any(|n| n.to_lowercase().contains(&name));
+        let outcome = match (&self.outcome, &record.winner) {
+            (None, _) => true,
+            (Some(Outcome::Won(ai_side)), Some(winner)) => ai_side == winner,
+            (Some(Outcome::Level), None) => true,
+            _ => false,
+        };
+        let mode = self
+            .mode
+            .as_ref()
+            .is_none_or(|mode| mem::discriminant(mode) == mem::discriminant(&record.mode));
+        named && outcome && mode
+    }
+
+    pub fn next_outcome(&mut self) {
+        self.outcome = match self.outcome {
+            None => Some(Outcome::Won(AiSide::For)),
+            Some(Outcome::Won(AiSide::For)) => Some(Outcome::Won(AiSide::Against)),
+            Some(Outcome::Won(AiSide::Against)) => Some(Outcome::Level),
+            Some(Outcome::Level) => None,
+        };
+    }
+}
+
+// Totals over a set of archived games
+pub struct Stats {
+    pub games: usize,
+    pub for_wins: usize,
+    pub against_wins: usize,
+    pub draws: usize,
+    pub average_duration: Duration,
+    pub average_turns: f64,
+    // Most points a winner came back from, with the game id
+    pub biggest_comeback: Option<(u32, u32)>,
+    // Both teams together
+    pub points: Vec<(Source, i64)>,
+    // Named players with the most wins, then the most games: (name, wins, games)
+    pub top_players: Vec<(String, usize, usize)>,
+}
+
+impl Stats {
+    pub fn new(records: &[&Record]) -> Stats {
+        let games = records.len();
+        let wins = |ai_side: Option<AiSide>| records.iter().filter(|r| r.winner == ai_side).count();
+        let total_secs: u64 = records.iter().map(|r| r.duration.as_secs()).sum();
+        let total_turns: usize = records.iter().map(|r| r.turns()).sum();
+
+        let biggest_comeback = records
+            .iter()
+            .map(|r| (r.comeback(), r.id))
+            .filter(|(deficit, _)| *deficit > 0)
+            .max_by_key(|(deficit, _)| *deficit);
+
+        let points = SOURCES
+            .iter()
+            .map(|source| {
+                let points = records
+                    .iter()
+                    .map(|r| {
+                        r.points_from(&AiSide::For, *source)
+                            + r.points_from(&AiSide::Against, *source)
+                    })
+                    .sum();
+                (*source, points)
+            })
+            .collect();
+
+        let mut players: Vec<(String, usize, usize)> = vec![];
+        for record in records {
+            for ai_side in [AiSide::For, AiSide::Against] {
+                let won = record.winner.as_ref() == Some(&ai_side);
+                for name in record
+                    .team(&ai_side)
+                    .players
+                    .iter()
+                    .filter(|n| !n.is_empty())
+                {
+                    match players.iter_mut().find(|(n, _, _)| n == name) {
+                        Some(player) => {
+                            player.1 += won as usize;
+                            player.2 += 1;
+                        }
+                        None => players.push((name.clone(), won as usize, 1)),
+                    }
+                }
+            }
+        }
+        let top_players = players
+            .into_iter()
+            .sorted_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)))
+            .take(TOP_PLAYERS)
+            .collect();
+
+        Stats {
+            games,
+            for_wins: wins(Some(AiSide::For)),
+            against_wins: wins(Some(AiSide::Against)),
+            draws: wins(None),
+            average_duration: Duration::from_secs(total_secs / games.max(1) as u64),
+            average_turns: total_turns as f64 / games.max(1) as f64,
+            biggest_comeback,
+            points,
+            top_players,
+        }
+    }
+}
+
+fn side_key(ai_side: Option<&AiSide>) -> &'static str {
+    match ai_side {
+        Some(AiSide::For) => "for",
+        Some(AiSide::Against) => "against",
+        None => "-",
+    }
+}
+
+fn side_from_key(key: &str) -> Option<Option<AiSide>> {
+    match key {
+        "for" => Some(Some(AiSide::For)),
+        "against" => Some(Some(AiSide::Against)),
+        "-" => Some(None),
+        _ => None,
+    }
+}
+
+fn moment_key(moment: &Moment) -> String {
+    let (kind, ai_side) = match moment {
+        Moment::Race(ai_side) => ("race", ai_side.as_ref()),
+        Moment::Challenge(ai_side) => ("challenge", ai_side.as_ref()),
+        Moment::Database(ai_side) => ("database", Some(ai_side)),
+    };
+    format!("{}:{}", kind, side_key(ai_side))
+}
+
+fn moment_from_key(key: &str) -> Option<Moment> {
+    let (kind, ai_side) = key.split_once(':')?;
+    let ai_side = side_from_key(ai_side)?;
+    Some(match kind {
+        "race" => Moment::Race(ai_side),
+        "challenge" => Moment::Challenge(ai_side),
+        "database" => Moment::Database(ai_side?),
+        _ => return None,
+    })
+}
+
+// Archived games, oldest first
+pub struct History {
+    records: Vec<Record>,
+    // Id of the next archived game, it only ever grows
+    next_id: u32,
+}
+
+impl History {
+    // Newest first
+    pub fn filtered(&self, filter: &Filter) -> Vec<&Record> {
+        self.records
+            .iter()
+            .rev()
+            .filter(|record| filter.matches(record))
+            .collect()
+    }
+
+    pub fn get(&self, id: u32) -> Option<&Record> {
+        self.records.iter().find(|record| record.id == id)
+    }
+
+    // Ids keep growing, a deleted game's id is never given again
+    pub fn add(&mut self, mut record: Record) -> u32 {
+        record.id = self.next_id;
+        self.next_id += 1;
+        let id = record.id;
+        self.records.push(record);
+        id
+    }
+
+    pub fn remove(&mut self, id: u32) -> bool {
+        let len = self.records.len();
+        self.records.retain(|record| record.id != id);
+        self.records.len() != len
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::write(HISTORY_FILE, self.to_text())
+    }
+
+    fn to_text(&self) -> String {
+        let mut content = format!("next\t{}\n\n", self.next_id);
+        for record in &self.records {
+            content += &format!(
+                "game\t{}\t{}\t{}\t{}\n",
+                record.id,
+                record.played,
+                record.duration.as_secs(),
+                side_key(record.winner.as_ref())
+            );
+            content += &format!("mode\t{}\n", record.mode.key());
+            for ai_side in [AiSide::For, AiSide::Against] {
+                let team = record.team(&ai_side);
+                content += &format!(
+                    "team\t{}\t{}\t{}\t{}\t{}",
+                    side_key(Some(&ai_side)),
+                    team.name.as_deref().unwrap_or_default(),
+                    team.nb_players,
+                    team.progress,
+                    team.nb_rounds
+                );
+                for player in &team.players {
+                    content += &format!("\t{}", player);
+                }
+                content += "\n";
+            }
+            if let Some(code) = &record.code {
+                content += &format!("code\t{}\n", code);
+            }
+            if let Some(seed) = record.seed {
+                content += &format!("seed\t{}\n", seed);
+            }
+            for rule in &record.rules {
+                content += &format!("rule\t{}\n", rule);
+            }
+            for snapshot in &record.timeline {
+                let moments = if snapshot.moments.is_empty() {
+                    "-".to_string()
+                } else {
+                    snapshot.moments.iter().map(moment_key).join(",")
+                };
+                content += &format!(
+                    "turn\t{}\t{}\t{}\n",
+                    snapshot.for_ai, snapshot.against_ai, moments
+                );
+            }
+            for score in &record.scores {
+                content += &format!(
+                    "score\t{}\t{}\t{}\t{}\n",
+                    score.turn,
+                    side_key(Some(&score.ai_side)),
+                    score.source.key(),
+                    score.points
+                );
+            }
+            content += "\n";
+        }
+        content
+    }
+
+    pub fn load() -> io::Result<History> {
+        match fs::read_to_string(HISTORY_FILE) {
+            Ok(content) => History::parse(&content),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(History::empty()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn parse(content: &str) -> io::Result<History> {
+        let invalid = || io::Error::new(ErrorKind::InvalidData, "corrupted history file");
+
+        let mut records: Vec<Record> = Vec::new();
+        let mut next_id = 1;
+        for line in content.lines().filter(|l| !l.is_empty()) {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if let ["next", id] = fields.as_slice() {
+                next_id = id.parse().map_err(|_| invalid())?;
+                continue;
+            }
+            if let ["game", id, played, duration, winner] = fields.as_slice() {
+                records.push(Record {
+                    id: id.parse().map_err(|_| invalid())?,
+                    played: played.to_string(),
+                    duration: Duration::from_secs(duration.parse().map_err(|_| invalid())?),
+                    winner: side_from_key(winner).ok_or_else(invalid)?,
+                    for_ai: Team::empty(),
+                    against_ai: Team::empty(),
+                    mode: GameMode::Target(0),
+                    rules: vec![],
+                    code: None,
+                    seed: None,
+                    timeline: vec![],
+                    scores: vec![],
+                });
+                continue;
+            }
+            // Every other line belongs to the last game
+            let record = records.last_mut().ok_or_else(invalid)?;
+            match fields.as_slice() {
+                ["mode", mode] => record.mode = GameMode::from_key(mode).ok_or_else(invalid)?,
+                ["team", ai_side, name, nb_players, progress, nb_rounds, players @ ..] => {
+                    let team = Team {
+                        name: Some(name.to_string()).filter(|name| !name.is_empty()),
+                        nb_players: nb_players.parse().map_err(|_| invalid())?,
+                        players: players.iter().map(|p| p.to_string()).collect(),
+                        progress: progress.parse().map_err(|_| invalid())?,
+                        nb_rounds: nb_rounds.parse().map_err(|_| invalid())?,
+                    };
+                    match side_from_key(ai_side) {
+                        Some(Some(AiSide::For)) => record.for_ai = team,
+                        Some(Some(AiSide::Against)) => record.against_ai = team,
+                        _ => return Err(invalid()),
+                    }
+                }
+                ["code", code] => record.code = Some(code.to_string()),
+                ["seed", seed] => record.seed = Some(seed.parse().map_err(|_| invalid())?),
+                ["rule", rule] => record.rules.push(rule.to_string()),
+                ["turn", for_ai, against_ai, moments] => record.timeline.push(Snapshot {
+                    for_ai: for_ai.parse().map_err(|_| invalid())?,
+                    against_ai: against_ai.parse().map_err(|_| invalid())?,
+                    moments: match *moments {
+                        "-" => vec![],
+                        moments => moments
+                            .split(',')
+                            .map(|key| moment_from_key(key).ok_or_else(invalid))
+                            .collect::<io::Result<_>>()?,
+                    },
+                }),
+                ["score", turn, ai_side, source, points] => record.scores.push(Score {
+                    turn: turn.parse().map_err(|_| invalid())?,
+                    ai_side: side_from_key(ai_side).flatten().ok_or_else(invalid)?,
+                    source: Source::from_key(source).ok_or_else(invalid)?,
+                    points: points.parse().map_err(|_| invalid())?,
+                }),
+                _ => return Err(invalid()),
+            }
+        }
+        // Files written before the counter was kept
+        let last_id = records.iter().map(|r| r.id).max().unwrap_or(0);
+        Ok(History {
+            records,
+            next_id: next_id.max(last_id + 1),
+        })
+    }
+
+    pub fn empty() -> History {
+        History {
+            records: Vec::new(),
+            next_id: 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::data::side::Side;
+
+    fn record() -> Record {
+        let mut game = Game::new();
+        game.for_ai = Some(Side::new(2));
+        game.against_ai = Some(Side::new(1));
+        game.begin();
+        game.score(&AiSide::For, Source::Races, 5);
+        game.mark(Moment::Race(Some(AiSide::For)));
+        game.end_turn();
+        game.finish();
+        Record::new(
+            &game,
+            Some(AiSide::For),
+            Some("0FD5F05".to_string()),
+            vec!["Rule".to_string()],
+        )
+    }
+
+    #[test]
+    fn deleted_ids_are_not_given_again() {
+        let mut history = History::empty();
+        assert_eq!(history.add(record()), 1);
+        assert_eq!(history.add(record()), 2);
+        assert!(history.remove(2));
+
+        let mut history = History::parse(&history.to_text()).unwrap();
+        assert_eq!(history.add(record()), 3);
+    }
+
+    #[test]
+    fn old_files_continue_after_the_last_id() {
+        let mut history = History::empty();
+        history.add(record());
+        history.add(record());
+        let text = history.to_text().replacen("next\t3\n", "", 1);
+
+        let mut history = History::parse(&text).unwrap();
+        assert_eq!(history.add(record()), 3);
+    }
+
+    #[test]
+    fn records_survive_a_round_trip() {
+        let mut history = History::empty();
+        let id = history.add(record());
+        let saved = history.get(id).unwrap();
+
+        let loaded = History::parse(&history.to_text()).unwrap();
+        let record = loaded.get(id).unwrap();
+        assert!(record.winner == saved.winner);
+        assert_eq!(record.code, saved.code);
+        assert_eq!(record.seed, saved.seed);
+        assert_eq!(record.rules, saved.rules);
+        assert_eq!(record.for_ai.nb_players, 2);
+        assert_eq!(record.against_ai.nb_players, 1);
+        assert_eq!(record.turns(), saved.turns());
+        assert_eq!(
+            record.points_from(&AiSide::For, Source::Races),
+            saved.points_from(&AiSide::For, Source::Races)
+        );
+        assert_eq!(history.to_text(), loaded.to_text());
+    }
+
+    #[test]
+    fn corrupted_files_are_refused() {
+        assert!(History::parse("turn\t1\t2\t-\n").is_err());
+        assert!(History::parse("game\t1\tdate\tnot a number\tfor\n").is_err());
+    }
+}
@@ -1,5 +1,17 @@
+pub mod board;
 pub mod capture;
+pub mod challenge;
+pub mod database;
+pub mod event;
 pub mod game;
+pub mod handicap;
+pub mod history;
 pub mod race;
+pub mod ratings;
+pub mod rules;
+pub mod score;
 pub mod side;
+pub mod timeline;
+pub mod tournament;
+pub mod venue;
 pub mod vpn;
@@ -1,23 +1,242 @@
-use std::time::{Duration, Instant};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
 
-use rand::Rng;
+use rand::seq::SliceRandom;
 
-use super::game::Position;
+use super::{
+    capture::ILLEGAL_CAPTURE_POSITIONS,
+    game::{AiSide, Position, COL_RANGE, ROW_RANGE},
+};
 
 pub const SECS_IN_MIN: u64 = 60;
 const RACE_DURATION: Duration = Duration::from_secs(2 * SECS_IN_MIN);
+// The reward decays from RACE_POINTS when the race starts to MIN_RACE_POINTS when it ends
 pub const RACE_POINTS: u32 = 5;
+const MIN_RACE_POINTS: u32 = 1;
+// Lost by both teams when nobody claims a race
+pub const RACE_MISS_PENALTY: u32 = 2;
+
+pub const MAX_RACES: usize = 3;
+
+// Balanced races accept targets whose distance gap is this close to the fairest one
+const BALANCE_TOLERANCE: u8 = 1;
+
+// Cells that cannot be walked through
+pub(super) const WALLS: &[Position] = &[
+    Position { row: 3, col: 6 },
+    Position { row: 4, col: 6 },
+    Position { row: 5, col: 6 },
+];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum RaceBalance {
+    // Any legal cell
+    Off,
+    Manhattan,
+    // Shortest walk around the middle walls
+    Path,
+}
+
+impl RaceBalance {
+    pub fn next(&self) -> RaceBalance {
+        match self {
+            RaceBalance::Off => RaceBalance::Manhattan,
+            RaceBalance::Manhattan => RaceBalance::Path,
+            RaceBalance::Path => RaceBalance::Off,
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<RaceBalance> {
+        match code {
+            "off" => Some(RaceBalance::Off),
+            "manhattan" => Some(RaceBalance::Manhattan),
+            "path" => Some(RaceBalance::Path),
+            _ => None,
+        }
+    }
+}
+
+impl AiSide {
+    pub fn base(&self) -> Position {
+        match self {
+            AiSide::For => Position { row: 4, col: 1 },
+            AiSide::Against => Position { row: 4, col: 11 },
+        }
+    }
+}
+
+pub(super) fn manhattan(a: &Position, b: &Position) -> u8 {
+    a.row.abs_diff(b.row) + a.col.abs_diff(b.col)
+}
+
+// Steps from the closest start to every cell, None when unreachable
+pub(super) type Steps = Vec<Vec<Option<u8>>>;
+
+pub(super) fn walk(starts: &[Position]) -> Steps {
+    let mut steps = vec![vec![None; *COL_RANGE.end() as usize + 1]; *ROW_RANGE.end() as usize + 1];
+    let mut queue = VecDeque::new();
+    for start in starts {
+        steps[start.row as usize][start.col as usize] = Some(0);
+        queue.push_back((*start, 0));
+    }
+    while let Some((position, step)) = queue.pop_front() {
+        let neighbours = [(-1, 0), (1, 0), (0, -1), (0, 1)].map(|(row, col): (i8, i8)| Position {
+            row: position.row.saturating_add_signed(row),
+            col: position.col.saturating_add_signed(col),
+        });
+        for next in neighbours {
+            if !ROW_RANGE.contains(&next.row)
+                || !COL_RANGE.contains(&next.col)
+                || WALLS.contains(&next)
+                || steps[next.row as usize][next.col as usize].is_some()
+            {
+                continue;
+            }
+            steps[next.row as usize][next.col as usize] = Some(step + 1);
+            queue.push_back((next, step + 1));
+        }
+    }
+    steps
+}
+
+// Where each team starts running from, its base and its VPN when known
+fn starts(ai_side: AiSide, vpns: Option<&(Position, Position)>) -> Vec<Position> {
+    let mut starts = vec![ai_side.base()];
+    if let Some((for_ai, against_ai)) = vpns {
+        starts.push(match ai_side {
+            AiSide::For => *for_ai,
+            AiSide::Against => *against_ai,
+        });
+    }
+    starts
+}
+
+// Distance of each team to every target
+struct Distances {
+    for_ai: Vec<Position>,
+    against_ai: Vec<Position>,
+    walks: Option<(Steps, Steps)>,
+}
+
+impl Distances {
+    fn new(vpns: Option<&(Position, Position)>, balance: RaceBalance) -> Distances {
+        let for_ai = starts(AiSide::For, vpns);
+        let against_ai = starts(AiSide::Against, vpns);
+        let walks = match balance {
+            RaceBalance::Manhattan => None,
+            _ => Some((walk(&for_ai), walk(&against_ai))),
+        };
+        Distances {
+            for_ai,
+            against_ai,
+            walks,
+        }
+    }
+
+    fn to(&self, target: &Position) -> Option<(u8, u8)> {
+        match &self.walks {
+            Some((for_ai, against_ai)) => Some((
+                for_ai[target.row as usize][target.col as usize]?,
+                against_ai[target.row as usize][target.col as usize]?,
+            )),
+            None => {
+                let closest =
+                    |starts: &[Position]| starts.iter().map(|start| manhattan(start, target)).min();
+                Some((closest(&self.for_ai)?, closest(&self.against_ai)?))
+            }
+        }
+    }
+}
+
+// How a race ended, winner is None when nobody claimed it
+pub struct RaceResult {
+    pub position: Position,
+    pub winner: Option<AiSide>,
+    pub took: Duration,
+    pub points: u32,
+}
 
 pub struct Race {
     start: Instant,
     pub position: Position,
+    // Distances of the For AI and Against AI teams to the target
+    pub distances: (u8, u8),
 }
 
 impl Race {
-    pub fn new() -> Race {
+    // taken holds the targets of the races already running
+    pub fn new(
+        vpns: Option<&(Position, Position)>,
+        balance: RaceBalance,
+        taken: &[Position],
+    ) -> Race {
+        let distances = Distances::new(vpns, balance);
+        // Neither bases, the middle, the VPNs nor the cells next to them
+        let targets: Vec<(Position, (u8, u8))> = ROW_RANGE
+            .flat_map(|row| COL_RANGE.map(move |col| Position { row, col }))
+            .filter(|position| {
+                !ILLEGAL_CAPTURE_POSITIONS.contains(position) && !taken.contains(position)
+            })
+            .filter(|position| {
+                vpns.is_none_or(|(for_ai, against_ai)| {
+                    manhattan(position, for_ai) > 1 && manhattan(position, against_ai) > 1
+                })
+            })
+            .filter_map(|position| Some((position, distances.to(&position)?)))
+            .collect();
+
+        let gap = |(for_ai, against_ai): &(u8, u8)| for_ai.abs_diff(*against_ai);
+        let fairest = targets.iter().map(|(_, distances)| gap(distances)).min();
+        let candidates: Vec<&(Position, (u8, u8))> = match (balance, fairest) {
+            (RaceBalance::Off, _) | (_, None) => targets.iter().collect(),
+            (_, Some(fairest)) => targets
+                .iter()
+                .filter(|(_, distances)| gap(distances) <= fairest + BALANCE_TOLERANCE)
+                .collect(),
+        };
+
+        let (position, distances) = **candidates
+            .choose(&mut rand::thread_rng())
+            .expect("the board has legal race targets");
         Race {
             start: Instant::now(),
-            position: rand::thread_rng().gen(),
+            position,
+            distances,
+        }
+    }
+
+    // 100 when both teams are as far from the target
+    pub fn fairness(&self) -> u32 {
+        let (for_ai, against_ai) = self.distances;
+        match for_ai.max(against_ai) {
+            0 => 100,
+            farthest => 100 * for_ai.min(against_ai) as u32 / farthest as u32,
+        }
+    }
+
+    pub fn reward(&self) -> u32 {
+        let left =
+            self.remaining_time().num_milliseconds() as f64 / RACE_DURATION.as_millis() as f64;
+        MIN_RACE_POINTS + ((RACE_POINTS - MIN_RACE_POINTS) as f64 * left).round() as u32
+    }
+
+    pub fn claim(self, winner: AiSide) -> RaceResult {
+        RaceResult {
+            position: self.position,
+            winner: Some(winner),
+            took: self.start.elapsed(),
+            points: self.reward(),
+        }
+    }
+
+    pub fn miss(self) -> RaceResult {
+        RaceResult {
+            position: self.position,
+            winner: None,
+            took: RACE_DURATION,
+            points: RACE_MISS_PENALTY,
         }
     }
 
@@ -33,3 +252,68 @@ impl Race {
         self.remaining_time().is_zero()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::data::{game::Game, score::Source, side::Side};
+
+    fn race(elapsed: Duration) -> Race {
+        Race {
+            start: Instant::now() - elapsed,
+            position: Position { row: 2, col: 6 },
+            distances: (4, 4),
+        }
+    }
+
+    #[test]
+    fn reward_decays_while_the_race_runs() {
+        assert_eq!(race(Duration::ZERO).reward(), RACE_POINTS);
+        assert_eq!(race(RACE_DURATION / 2).reward(), 3);
+        assert_eq!(race(RACE_DURATION).reward(), MIN_RACE_POINTS);
+        assert_eq!(race(2 * RACE_DURATION).reward(), MIN_RACE_POINTS);
+        assert!(race(RACE_DURATION).is_finished());
+    }
+
+    #[test]
+    fn missed_races_cost_both_teams() {
+        let mut game = Game::new();
+        game.for_ai = Some(Side::new(2));
+        game.against_ai = Some(Side::new(2));
+        game.score(&AiSide::For, Source::Captures, 10);
+        game.score(&AiSide::Against, Source::Captures, 1);
+        game.races = vec![race(RACE_DURATION), race(Duration::ZERO)];
+        game.expire_races();
+
+        assert_eq!(game.races.len(), 1);
+        assert_eq!(
+            game.for_ai.as_ref().unwrap().progress(),
+            10 - RACE_MISS_PENALTY
+        );
+        assert_eq!(game.against_ai.as_ref().unwrap().progress(), 0);
+        assert_eq!(game.points_from(&AiSide::Against, Source::Penalties), -1);
+        assert!(game.race_history[0].winner.is_none());
+    }
+
+    #[test]
+    fn fairness_compares_the_distances() {
+        let mut unfair = race(Duration::ZERO);
+        unfair.distances = (2, 8);
+        assert_eq!(race(Duration::ZERO).fairness(), 100);
+        assert_eq!(unfair.fairness(), 25);
+    }
+
+    #[test]
+    fn targets_avoid_bases_walls_vpns_and_running_races() {
+        let vpns = (Position { row: 1, col: 2 }, Position { row: 7, col: 10 });
+        let taken = [Position { row: 2, col: 3 }];
+        for _ in 0..200 {
+            let race = Race::new(Some(&vpns), RaceBalance::Path, &taken);
+            assert!(!ILLEGAL_CAPTURE_POSITIONS.contains(&race.position));
+            assert!(!taken.contains(&race.position));
+            assert!(manhattan(&race.position, &vpns.0) > 1);
+            assert!(manhattan(&race.position, &vpns.1) > 1);
+            assert!(race.distances.0.abs_diff(race.distances.1) <= BALANCE_TOLERANCE);
+        }
+    }
+}
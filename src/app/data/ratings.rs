@@ -0,0 +1,175 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+};
+
+use itertools::Itertools;
+
+use super::game::AiSide;
+
+pub const RATINGS_FILE: &str = "cyberconnect-players.txt";
+pub const START_RATING: f64 = 1000.0;
+pub const MAX_PLAYER_NAME: usize = 16;
+
+// Elo constants
+const K_FACTOR: f64 = 32.0;
+const SCALE: f64 = 400.0;
+
+pub struct Player {
+    pub name: String,
+    pub rating: f64,
+    pub games: u32,
+    pub wins: u32,
+}
+
+pub struct Ratings {
+    players: Vec<Player>,
+}
+
+impl Ratings {
+    pub fn rating(&self, name: &str) -> f64 {
+        self.players
+            .iter()
+            .find(|p| p.name == name)
+            .map_or(START_RATING, |p| p.rating)
+    }
+
+    fn team_rating(&self, team: &[String]) -> f64 {
+        if team.is_empty() {
+            return START_RATING;
+        }
+        team.iter().map(|name| self.rating(name)).sum::<f64>() / team.len() as f64
+    }
+
+    fn player_mut(&mut self, name: &str) -> &mut Player {
+        let index = match self.players.iter().position(|p| p.name == name) {
+            Some(index) => index,
+            None => {
+                self.players.push(Player {
+                    name: name.to_string(),
+                    rating: START_RATING,
+                    games: 0,
+                    wins: 0,
+                });
+                self.players.len() - 1
+            }
+        };
+        &mut self.players[index]
+    }
+
+    // Every player of a team moves by the same amount, based on the team averages
+    pub fn update(&mut self, for_ai: &[String], against_ai: &[String], winner: Option<AiSide>) {
+        let expected = 1.0
+            / (1.0 + 10f64.powf((self.team_rating(against_ai) - self.team_rating(for_ai)) / SCALE));
+        let score = match winner {
+            Some(AiSide::For) => 1.0,
+            Some(AiSide::Against) => 0.0,
+            None => 0.5,
+        };
+        let delta = K_FACTOR * (score - expected);
+
+        for (team, delta, won) in [
+            (for_ai, delta, winner == Some(AiSide::For)),
+            (against_ai, -delta, winner == Some(AiSide::Against)),
+        ] {
+            for name in team {
+                let player = self.player_mut(name);
+                player.rating += delta;
+                player.games += 1;
+                if won {
+                    player.wins += 1;
+                }
+            }
+        }
+    }
+
+    pub fn leaderboard(&self) -> Vec<&Player> {
+        self.players
+            .iter()
+            .sorted_by(|a, b| b.rating.total_cmp(&a.rating))
+            .collect()
+    }
+
+    // Split the players into teams of the given size with the closest average ratings,
+    // by position in the roster so that players sharing a name both get a slot
+    pub fn balance(&self, players: &[String], for_size: usize) -> (Vec<String>, Vec<String>) {
+        let mut best = (f64::MAX, Vec::new(), Vec::new());
+        for picked in (0..players.len()).combinations(for_size) {
+            let team = |in_for_ai: bool| -> Vec<String> {
+                players
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| picked.contains(index) == in_for_ai)
+                    .map(|(_, name)| name.clone())
+                    .collect()
+            };
+            let (for_ai, against_ai) = (team(true), team(false));
+            let diff = (self.team_rating(&for_ai) - self.team_rating(&against_ai)).abs();
+            if diff < best.0 {
+                best = (diff, for_ai, against_ai);
+            }
+        }
+        (best.1, best.2)
+    }
+
+    pub fn rating_gap(&self, for_ai: &[String], against_ai: &[String]) -> f64 {
+        self.team_rating(for_ai) - self.team_rating(against_ai)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let content: String = self
+            .players
+            .iter()
+            .map(|p| format!("{}\t{:.1}\t{}\t{}\n", p.name, p.rating, p.games, p.wins))
+            .collect();
+        fs::write(RATINGS_FILE, content)
+    }
+
+    pub fn load() -> io::Result<Ratings> {
+        let content = match fs::read_to_string(RATINGS_FILE) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+        let invalid = || io::Error::new(ErrorKind::InvalidData, "corrupted players file");
+
+        let mut players = Vec::new();
+        for line in content.lines().filter(|l| !l.is_empty()) {
+            match line.split('\t').collect::<Vec<_>>().as_slice() {
+                [name, rating, games, wins] => players.push(Player {
+                    name: name.to_string(),
+                    rating: rating.parse().map_err(|_| invalid())?,
+                    games: games.parse().map_err(|_| invalid())?,
+                    wins: wins.parse().map_err(|_| invalid())?,
+                }),
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(Ratings { players })
+    }
+
+    pub fn empty() -> Ratings {
+        Ratings {
+            players: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balance_keeps_players_sharing_a_name() {
+        let players: Vec<String> = ["Alex", "Alex", "Sam", "Kim"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        let (for_ai, against_ai) = Ratings::empty().balance(&players, 2);
+        assert_eq!(for_ai.len(), 2);
+        assert_eq!(against_ai.len(), 2);
+        let mut all = [for_ai, against_ai].concat();
+        all.sort();
+        assert_eq!(all, ["Alex", "Alex", "Kim", "Sam"]);
+    }
+}
@@ -0,0 +1,220 @@
+use std::time::Duration;
+
+use super::{
+    board::Constraints,
+    capture::Transfer,
+    challenge::TieRule,
+    database::{Raid, DEFAULT_DATABASE_SUPPLY, MAX_DATABASE_SUPPLY},
+    event::EventRate,
+    race::{RaceBalance, MAX_RACES, SECS_IN_MIN},
+    side::TOTAL_PROGRESS,
+    venue::Venue,
+    vpn::{MAX_VPN_COOLDOWN, MAX_VPN_RELOCATE, MAX_VPN_USES},
+};
+use crate::config;
+
+pub const MIN_TARGET: u32 = 10;
+const TARGET_STEP: u32 = 5;
+
+pub const DEFAULT_ROUNDS: u32 = 10;
+pub const MAX_ROUNDS: u32 = 50;
+
+pub const DEFAULT_MINUTES: u32 = 30;
+const MINUTES_STEP: u32 = 5;
+pub const MAX_MINUTES: u32 = 180;
+
+#[derive(Clone, PartialEq)]
+pub enum GameMode {
+    // First team to reach the target progress wins
+    Target(u32),
+    // Highest progress once both teams played that many rounds
+    Rounds(u32),
+    // Highest progress when the time (in minutes) runs out
+    Timed(u32),
+}
+
+impl GameMode {
+    pub fn next(&self) -> GameMode {
+        match self {
+            GameMode::Target(_) => GameMode::Rounds(DEFAULT_ROUNDS),
+            GameMode::Rounds(_) => GameMode::Timed(DEFAULT_MINUTES),
+            GameMode::Timed(_) => GameMode::Target(TOTAL_PROGRESS),
+        }
+    }
+
+    pub fn previous(&self) -> GameMode {
+        match self {
+            GameMode::Target(_) => GameMode::Timed(DEFAULT_MINUTES),
+            GameMode::Rounds(_) => GameMode::Target(TOTAL_PROGRESS),
+            GameMode::Timed(_) => GameMode::Rounds(DEFAULT_ROUNDS),
+        }
+    }
+
+    pub fn increase(&mut self) {
+        match self {
+            GameMode::Target(target) => *target = (*target + TARGET_STEP).min(TOTAL_PROGRESS),
+            GameMode::Rounds(rounds) => *rounds = (*rounds + 1).min(MAX_ROUNDS),
+            GameMode::Timed(minutes) => *minutes = (*minutes + MINUTES_STEP).min(MAX_MINUTES),
+        }
+    }
+
+    pub fn decrease(&mut self) {
+        match self {
+            GameMode::Target(target) => {
+                *target = target.saturating_sub(TARGET_STEP).max(MIN_TARGET)
+            }
+            GameMode::Rounds(rounds) => *rounds = rounds.saturating_sub(1).max(1),
+            GameMode::Timed(minutes) => {
+                *minutes = minutes.saturating_sub(MINUTES_STEP).max(MINUTES_STEP)
+            }
+        }
+    }
+
+    // "target:101", "rounds:10" or "timed:30"
+    pub fn key(&self) -> String {
+        match self {
+            GameMode::Target(target) => format!("target:{}", target),
+            GameMode::Rounds(rounds) => format!("rounds:{}", rounds),
+            GameMode::Timed(minutes) => format!("timed:{}", minutes),
+        }
+    }
+
+    // The value is optional, the default one of the kind is used without it
+    pub fn from_key(key: &str) -> Option<GameMode> {
+        let (kind, value) = match key.split_once(':') {
+            Some((kind, value)) => (kind, Some(value.parse::<u32>().ok()?)),
+            None => (key, None),
+        };
+        Some(match kind {
+            "target" => GameMode::Target(
+                value.map_or(TOTAL_PROGRESS, |v| v.clamp(MIN_TARGET, TOTAL_PROGRESS)),
+            ),
+            "rounds" => GameMode::Rounds(value.map_or(DEFAULT_ROUNDS, |v| v.clamp(1, MAX_ROUNDS))),
+            "timed" => GameMode::Timed(
+                value.map_or(DEFAULT_MINUTES, |v| v.clamp(MINUTES_STEP, MAX_MINUTES)),
+            ),
+            _ => return None,
+        })
+    }
+
+    // Progress needed to fill a gauge
+    pub fn goal(&self) -> u32 {
+        match self {
+            GameMode::Target(target) => *target,
+            _ => TOTAL_PROGRESS,
+        }
+    }
+
+    // Highest progress a team can reach, only a target stops the game there
+    pub fn cap(&self) -> Option<u32> {
+        match self {
+            GameMode::Target(_) => Some(TOTAL_PROGRESS),
+            _ => None,
+        }
+    }
+}
+
+pub struct Rules {
+    pub mode: GameMode,
+    // Play a challenge to break ties instead of ending on a draw
+    pub sudden_death: bool,
+    // Only the challenges playable there are drawn
+    pub venue: Venue,
+    // Settles tied votes of judged challenges
+    pub tie_rule: TieRule,
+    // How race targets are placed between the teams
+    pub race_balance: RaceBalance,
+    // Races running at the same time
+    pub max_races: usize,
+    // How often Lucy events are drawn
+    pub event_rate: EventRate,
+    // How capture points change hands
+    pub capture_transfer: Transfer,
+    // What the generated board setup respects
+    pub board: Constraints,
+    // Databases each team can place
+    pub database_supply: u32,
+    // What a team can do to the other team's databases
+    pub raid: Raid,
+    // Teleports allowed per team and round
+    pub vpn_uses: u32,
+    // Rounds a VPN stays off after a round it was used in
+    pub vpn_cooldown: u32,
+    // Uses before a VPN moves to another row, 0 never moves it
+    pub vpn_relocate: u32,
+}
+
+impl Rules {
+    pub fn new() -> Rules {
+        Rules {
+            mode: GameMode::Target(TOTAL_PROGRESS),
+            sudden_death: true,
+            venue: Venue::new(),
+            tie_rule: config::value("tie_rule")
+                .as_deref()
+                .and_then(TieRule::from_code)
+                .unwrap_or(TieRule::Draw),
+            race_balance: config::value("race_balance")
+                .as_deref()
+                .and_then(RaceBalance::from_code)
+                .unwrap_or(RaceBalance::Path),
+            max_races: config::value("max_races")
+                .and_then(|races| races.parse().ok())
+                .map_or(1, |races: usize| races.clamp(1, MAX_RACES)),
+            event_rate: config::value("lucy_events")
+                .as_deref()
+                .and_then(EventRate::from_code)
+                .unwrap_or(EventRate::Normal),
+            capture_transfer: config::value("capture_transfer")
+                .as_deref()
+                .and_then(Transfer::from_code)
+                .unwrap_or(Transfer::Majority),
+            board: Constraints::new(),
+            database_supply: config::value("database_supply")
+                .and_then(|supply| supply.parse().ok())
+                .map_or(DEFAULT_DATABASE_SUPPLY, |supply: u32| {
+                    supply.clamp(1, MAX_DATABASE_SUPPLY)
+                }),
+            raid: config::value("database_raid")
+                .as_deref()
+                .and_then(Raid::from_code)
+                .unwrap_or(Raid::Off),
+            vpn_uses: config::value("vpn_uses")
+                .and_then(|uses| uses.parse().ok())
+                .map_or(1, |uses: u32| uses.clamp(1, MAX_VPN_USES)),
+            vpn_cooldown: config::value("vpn_cooldown")
+                .and_then(|rounds| rounds.parse().ok())
+                .map_or(0, |rounds: u32| rounds.min(MAX_VPN_COOLDOWN)),
+            vpn_relocate: config::value("vpn_relocate")
+                .and_then(|uses| uses.parse().ok())
+                .map_or(3, |uses: u32| uses.min(MAX_VPN_RELOCATE)),
+        }
+    }
+
+    pub fn next_max_races(&mut self) {
+        self.max_races = self.max_races % MAX_RACES + 1;
+    }
+
+    pub fn next_database_supply(&mut self) {
+        self.database_supply = self.database_supply % MAX_DATABASE_SUPPLY + 1;
+    }
+
+    pub fn next_vpn_uses(&mut self) {
+        self.vpn_uses = self.vpn_uses % MAX_VPN_USES + 1;
+    }
+
+    pub fn next_vpn_cooldown(&mut self) {
+        self.vpn_cooldown = (self.vpn_cooldown + 1) % (MAX_VPN_COOLDOWN + 1);
+    }
+
+    pub fn next_vpn_relocate(&mut self) {
+        self.vpn_relocate = (self.vpn_relocate + 1) % (MAX_VPN_RELOCATE + 1);
+    }
+
+    pub fn time_limit(&self) -> Option<Duration> {
+        match self.mode {
+            GameMode::Timed(minutes) => Some(Duration::from_secs(minutes as u64 * SECS_IN_MIN)),
+            _ => None,
+        }
+    }
+}
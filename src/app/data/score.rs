@@ -0,0 +1,91 @@
+use super::game::{AiSide, Game};
+
+// Where progress comes from, for the end of game report
+#[derive(Clone, Copy, PartialEq)]
+pub enum Source {
+    Handicap,
+    // Home capture points
+    Captures,
+    Center,
+    Races,
+    Databases,
+    Challenges,
+    // Lucy events moving points between the teams
+    Events,
+    // Missed races and points taken back by the referee
+    Penalties,
+}
+
+pub const SOURCES: &[Source] = &[
+    Source::Handicap,
+    Source::Captures,
+    Source::Center,
+    Source::Races,
+    Source::Databases,
+    Source::Challenges,
+    Source::Events,
+    Source::Penalties,
+];
+
+impl Source {
+    pub fn key(&self) -> &str {
+        match self {
+            Source::Handicap => "handicap",
+            Source::Captures => "captures",
+            Source::Center => "center",
+            Source::Races => "races",
+            Source::Databases => "databases",
+            Source::Challenges => "challenges",
+            Source::Events => "events",
+            Source::Penalties => "penalties",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Source> {
+        SOURCES.iter().find(|source| source.key() == key).copied()
+    }
+}
+
+// One change of a team's progress
+#[derive(Clone)]
+pub struct Score {
+    // Turn it is counted in, 0 for the handicap given when the game begins
+    pub turn: usize,
+    pub ai_side: AiSide,
+    pub source: Source,
+    pub points: i64,
+}
+
+impl Game {
+    // Negative points move the team back, only what the progress bounds let through is recorded
+    pub fn score(&mut self, ai_side: &AiSide, source: Source, points: i64) {
+        let cap = self.rules.mode.cap();
+        let side = match self.side_mut(ai_side) {
+            Some(side) => side,
+            None => return,
+        };
+        let before = side.progress();
+        if points >= 0 {
+            side.advance(points as u32, cap);
+        } else {
+            side.retreat(points.unsigned_abs() as u32);
+        }
+        let points = side.progress() as i64 - before as i64;
+        if points != 0 {
+            self.scores.push(Score {
+                turn: self.timeline.len(),
+                ai_side: ai_side.clone(),
+                source,
+                points,
+            });
+        }
+    }
+
+    pub fn points_from(&self, ai_side: &AiSide, source: Source) -> i64 {
+        self.scores
+            .iter()
+            .filter(|score| score.ai_side == *ai_side && score.source == source)
+            .map(|score| score.points)
+            .sum()
+    }
+}
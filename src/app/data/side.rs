@@ -1,29 +1,42 @@
-use super::{capture::Capture, game::AiSide};
+use super::handicap::Handicap;
 
 pub const TOTAL_PROGRESS: u32 = 101;
 
 pub struct Side {
     pub nb_players: u8,
+    // Player names, used for ratings when all of them are filled in
+    pub players: Vec<String>,
     progress: u32,
     pub nb_rounds: u32,
-    pub capture: Capture,
+    pub handicap: Handicap,
 }
 
 impl Side {
-    pub fn new(nb_players: u8, ai_side: AiSide) -> Side {
+    pub fn new(nb_players: u8) -> Side {
         Side {
             nb_players,
+            players: vec![String::new(); nb_players as usize],
             nb_rounds: 0,
             progress: 0,
-            capture: Capture::new(ai_side),
+            handicap: Handicap::none(),
         }
     }
 
-    pub fn advance(&mut self, value: u32) {
-        if self.progress + value > TOTAL_PROGRESS {
-            self.progress = TOTAL_PROGRESS;
-        } else {
-            self.progress += value;
+    // Same players and handicap, back at the start
+    pub fn rematch(&self) -> Side {
+        Side {
+            nb_players: self.nb_players,
+            players: self.players.clone(),
+            nb_rounds: 0,
+            progress: 0,
+            handicap: self.handicap.clone(),
+        }
+    }
+
+    pub fn advance(&mut self, value: u32, cap: Option<u32>) {
+        self.progress = self.progress.saturating_add(value);
+        if let Some(cap) = cap {
+            self.progress = self.progress.min(cap);
         }
     }
 
@@ -39,7 +52,11 @@ impl Side {
         self.progress
     }
 
-    pub fn has_won(&self) -> bool {
-        self.progress() >= TOTAL_PROGRESS
+    pub fn roster(&self) -> Option<Vec<String>> {
+        let names: Vec<String> = self.players.iter().map(|p| p.trim().to_string()).collect();
+        if names.iter().any(|name| name.is_empty()) {
+            return None;
+        }
+        Some(names)
     }
 }
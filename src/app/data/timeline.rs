@@ -0,0 +1,125 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    mem,
+};
+
+use super::game::{AiSide, Game};
+
+// Something that moved the progress during a turn, with the team it helped
+#[derive(Clone)]
+pub enum Moment {
+    // None when nobody claimed it in time
+    Race(Option<AiSide>),
+    // None on a draw or when both teams failed
+    Challenge(Option<AiSide>),
+    // Placed or raided by the team
+    Database(AiSide),
+}
+
+impl Moment {
+    pub fn team(&self) -> Option<&AiSide> {
+        match self {
+            Moment::Race(team) | Moment::Challenge(team) => team.as_ref(),
+            Moment::Database(ai_side) => Some(ai_side),
+        }
+    }
+}
+
+// Progress of both teams when a turn ended, and what happened during it
+#[derive(Clone)]
+pub struct Snapshot {
+    pub for_ai: u32,
+    pub against_ai: u32,
+    pub moments: Vec<Moment>,
+}
+
+impl Snapshot {
+    pub fn progress(&self, ai_side: &AiSide) -> u32 {
+        match ai_side {
+            AiSide::For => self.for_ai,
+            AiSide::Against => self.against_ai,
+        }
+    }
+
+    // Team ahead and by how much, None when level
+    fn leader(&self) -> Option<(AiSide, u32)> {
+        match self.for_ai.cmp(&self.against_ai) {
+            Ordering::Greater => Some((AiSide::For, self.for_ai - self.against_ai)),
+            Ordering::Less => Some((AiSide::Against, self.against_ai - self.for_ai)),
+            Ordering::Equal => None,
+        }
+    }
+}
+
+impl Game {
+    // Called when the game begins, at every end of turn and once it is over
+    pub fn snapshot(&mut self) {
+        let progress = |ai_side: &AiSide| match ai_side {
+            AiSide::For => self.for_ai.as_ref().map_or(0, |side| side.progress()),
+            AiSide::Against => self.against_ai.as_ref().map_or(0, |side| side.progress()),
+        };
+        let snapshot = Snapshot {
+            for_ai: progress(&AiSide::For),
+            against_ai: progress(&AiSide::Against),
+            moments: mem::take(&mut self.moments),
+        };
+        self.timeline.push(snapshot);
+    }
+
+    pub fn mark(&mut self, moment: Moment) {
+        self.moments.push(moment);
+    }
+}
+
+// Widest gap of the game, with the team ahead and the turn it happened
+pub fn largest_lead(timeline: &[Snapshot]) -> Option<(AiSide, u32, usize)> {
+    timeline
+        .iter()
+        .enumerate()
+        .filter_map(|(turn, snapshot)| {
+            let (ai_side, lead) = snapshot.leader()?;
+            Some((ai_side, lead, turn))
+        })
+        .max_by_key(|(_, lead, turn)| (*lead, Reverse(*turn)))
+}
+
+// Turns where the other team took the lead, level turns do not count
+pub fn lead_changes(timeline: &[Snapshot]) -> Vec<(usize, AiSide)> {
+    let mut leader = None;
+    let mut changes = vec![];
+    for (turn, snapshot) in timeline.iter().enumerate() {
+        if let Some((ai_side, _)) = snapshot.leader() {
+            if leader.as_ref().is_some_and(|leader| *leader != ai_side) {
+                changes.push((turn, ai_side.clone()));
+            }
+            leader = Some(ai_side);
+        }
+    }
+    changes
+}
+
+// Most points the team was ever behind
+pub fn deepest_deficit(timeline: &[Snapshot], ai_side: &AiSide) -> u32 {
+    timeline
+        .iter()
+        .filter_map(|snapshot| match snapshot.leader() {
+            Some((leader, lead)) if leader != *ai_side => Some(lead),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+// Challenges won by the For AI team, by the Against AI team, and without a winner
+pub fn challenge_record(timeline: &[Snapshot]) -> (usize, usize, usize) {
+    let mut record = (0, 0, 0);
+    for moment in timeline.iter().flat_map(|snapshot| &snapshot.moments) {
+        match moment {
+            Moment::Challenge(Some(AiSide::For)) => record.0 += 1,
+            Moment::Challenge(Some(AiSide::Against)) => record.1 += 1,
+            Moment::Challenge(None) => record.2 += 1,
+            _ => {}
+        }
+    }
+    record
+}
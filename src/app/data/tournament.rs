@@ -0,0 +1,402 @@
+use std::{
+    cmp::Reverse,
+    fs,
+    io::{self, ErrorKind},
+};
+
+use rand::seq::SliceRandom;
+
+use super::game::AiSide;
+
+pub const TOURNAMENT_FILE: &str = "cyberconnect-tournament.txt";
+pub const MIN_TEAMS: usize = 2;
+pub const MAX_TEAMS: usize = 16;
+pub const MAX_TEAM_NAME: usize = 20;
+
+const WIN_POINTS: u32 = 3;
+const DRAW_POINTS: u32 = 1;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    RoundRobin,
+    Bracket,
+}
+
+impl Format {
+    pub fn switch(&self) -> Format {
+        match self {
+            Format::RoundRobin => Format::Bracket,
+            Format::Bracket => Format::RoundRobin,
+        }
+    }
+
+    fn key(&self) -> &str {
+        match self {
+            Format::RoundRobin => "round-robin",
+            Format::Bracket => "bracket",
+        }
+    }
+}
+
+pub struct MatchResult {
+    pub home_progress: u32,
+    pub away_progress: u32,
+    // None on a draw
+    pub winner: Option<AiSide>,
+}
+
+// The home team plays For AI, the away team Against AI
+pub struct Match {
+    pub round: u32,
+    pub home: usize,
+    pub away: usize,
+    pub result: Option<MatchResult>,
+}
+
+impl Match {
+    fn winner(&self) -> Option<usize> {
+        match self.result.as_ref()?.winner.as_ref()? {
+            AiSide::For => Some(self.home),
+            AiSide::Against => Some(self.away),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Standing {
+    pub team: usize,
+    pub played: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub differential: i64,
+}
+
+impl Standing {
+    pub fn points(&self) -> u32 {
+        self.wins * WIN_POINTS + self.draws * DRAW_POINTS
+    }
+}
+
+pub struct Registration {
+    pub format: Format,
+    pub teams: Vec<String>,
+}
+
+impl Registration {
+    pub fn new() -> Registration {
+        Registration {
+            format: Format::RoundRobin,
+            teams: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, name: &str) {
+        let name = name.trim();
+        if !name.is_empty() && self.teams.len() < MAX_TEAMS && !self.teams.iter().any(|t| t == name)
+        {
+            self.teams.push(name.to_string());
+        }
+    }
+}
+
+pub struct Tournament {
+    pub format: Format,
+    pub teams: Vec<String>,
+    pub matches: Vec<Match>,
+}
+
+impl Tournament {
+    pub fn new(format: Format, teams: Vec<String>) -> Tournament {
+        let mut tournament = Tournament {
+            format,
+            teams,
+            matches: Vec::new(),
+        };
+
+        let mut order: Vec<usize> = (0..tournament.teams.len()).collect();
+        order.shuffle(&mut rand::thread_rng());
+        match format {
+            Format::RoundRobin => {
+                for (i, &home) in order.iter().enumerate() {
+                    for (j, &away) in order.iter().enumerate().skip(i + 1) {
+                        // Alternate sides so nobody always plays For AI
+                        let (home, away) = if (i + j) % 2 == 0 {
+                            (home, away)
+                        } else {
+                            (away, home)
+                        };
+                        tournament.matches.push(Match {
+                            round: 1,
+                            home,
+                            away,
+                            result: None,
+                        });
+                    }
+                }
+                tournament.matches.shuffle(&mut rand::thread_rng());
+            }
+            Format::Bracket => tournament.schedule_round(1, &order),
+        }
+
+        tournament
+    }
+
+    // Pairs the teams, an odd one out gets a bye to the next round
+    fn schedule_round(&mut self, round: u32, alive: &[usize]) {
+        for pair in alive.chunks_exact(2) {
+            self.matches.push(Match {
+                round,
+                home: pair[0],
+                away: pair[1],
+                result: None,
+            });
+        }
+    }
+
+    fn alive(&self, round: u32) -> Vec<usize> {
+        if round == 1 {
+            return (0..self.teams.len()).collect();
+        }
+        let previous = self.alive(round - 1);
+        let mut eliminated = Vec::new();
+        for m in self.matches.iter().filter(|m| m.round == round - 1) {
+            if let Some(winner) = m.winner() {
+                eliminated.push(if winner == m.home { m.away } else { m.home });
+            }
+        }
+        previous
+            .into_iter()
+            .filter(|team| !eliminated.contains(team))
+            .collect()
+    }
+
+    fn last_round(&self) -> u32 {
+        self.matches.iter().map(|m| m.round).max().unwrap_or(1)
+    }
+
+    pub fn next_match(&mut self) -> Option<usize> {
+        if let Some(index) = self.matches.iter().position(|m| m.result.is_none()) {
+            return Some(index);
+        }
+        if let Format::Bracket = self.format {
+            let round = self.last_round() + 1;
+            let alive = self.alive(round);
+            if alive.len() > 1 {
+                // Reshuffle so the same team doesn't always get the bye
+                let mut alive = alive;
+                alive.shuffle(&mut rand::thread_rng());
+                self.schedule_round(round, &alive);
+                return self.next_match();
+            }
+        }
+        None
+    }
+
+    pub fn record(&mut self, index: usize, result: MatchResult) {
+        let replay = result.winner.is_none() && self.format == Format::Bracket;
+        let m = &mut self.matches[index];
+        m.result = Some(result);
+        if replay {
+            // A bracket match needs a winner, so a draw is played again with swapped sides
+            let (round, home, away) = (m.round, m.away, m.home);
+            self.matches.push(Match {
+                round,
+                home,
+                away,
+                result: None,
+            });
+        }
+    }
+
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = (0..self.teams.len())
+            .map(|team| Standing {
+                team,
+                ..Default::default()
+            })
+            .collect();
+
+        for m in &self.matches {
+            if let Some(result) = &m.result {
+                let diff = result.home_progress as i64 - result.away_progress as i64;
+                standings[m.home].played += 1;
+                standings[m.away].played += 1;
+                standings[m.home].differential += diff;
+                standings[m.away].differential -= diff;
+                match result.winner {
+                    Some(AiSide::For) => {
+                        standings[m.home].wins += 1;
+                        standings[m.away].losses += 1;
+                    }
+                    Some(AiSide::Against) => {
+                        standings[m.away].wins += 1;
+                        standings[m.home].losses += 1;
+                    }
+                    None => {
+                        standings[m.home].draws += 1;
+                        standings[m.away].draws += 1;
+                    }
+                }
+            }
+        }
+
+        standings.sort_by_key(|s| (Reverse(s.points()), Reverse(s.differential)));
+        standings
+    }
+
+    pub fn champion(&mut self) -> Option<usize> {
+        if self.next_match().is_some() {
+            return None;
+        }
+        match self.format {
+            Format::RoundRobin => self.standings().first().map(|s| s.team),
+            Format::Bracket => self.alive(self.last_round() + 1).first().copied(),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::write(TOURNAMENT_FILE, self.to_text())
+    }
+
+    fn to_text(&self) -> String {
+        let mut content = format!("format\t{}\n", self.format.key());
+        for team in &self.teams {
+            content += &format!("team\t{}\n", team);
+        }
+        for m in &self.matches {
+            let result = match &m.result {
+                Some(result) => format!(
+                    "{}:{}:{}",
+                    result.home_progress,
+                    result.away_progress,
+                    match result.winner {
+                        Some(AiSide::For) => "for",
+                        Some(AiSide::Against) => "against",
+                        None => "draw",
+                    }
+                ),
+                None => "-".to_string(),
+            };
+            content += &format!("match\t{}\t{}\t{}\t{}\n", m.round, m.home, m.away, result);
+        }
+        content
+    }
+
+    pub fn load() -> io::Result<Option<Tournament>> {
+        match fs::read_to_string(TOURNAMENT_FILE) {
+            Ok(content) => Tournament::parse(&content).map(Some),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn parse(content: &str) -> io::Result<Tournament> {
+        let invalid = || io::Error::new(ErrorKind::InvalidData, "corrupted tournament file");
+
+        let mut tournament = Tournament {
+            format: Format::RoundRobin,
+            teams: Vec::new(),
+            matches: Vec::new(),
+        };
+        for line in content.lines() {
+            let fields: Vec<&str> = line.split('\t').collect();
+            match fields.as_slice() {
+                ["format", "bracket"] => tournament.format = Format::Bracket,
+                ["format", _] => tournament.format = Format::RoundRobin,
+                ["team", name] => tournament.teams.push(name.to_string()),
+                ["match", round, home, away, result] => {
+                    let parse = |s: &str| s.parse::<usize>().map_err(|_| invalid());
+                    let result = match result.split(':').collect::<Vec<_>>().as_slice() {
+                        [home_progress, away_progress, winner] => Some(MatchResult {
+                            home_progress: parse(home_progress)? as u32,
+                            away_progress: parse(away_progress)? as u32,
+                            winner: match *winner {
+                                "for" => Some(AiSide::For),
+                                "against" => Some(AiSide::Against),
+                                _ => None,
+                            },
+                        }),
+                        _ => None,
+                    };
+                    let (home, away) = (parse(home)?, parse(away)?);
+                    if home >= tournament.teams.len() || away >= tournament.teams.len() {
+                        return Err(invalid());
+                    }
+                    tournament.matches.push(Match {
+                        round: parse(round)? as u32,
+                        home,
+                        away,
+                        result,
+                    });
+                }
+                [""] => {}
+                _ => return Err(invalid()),
+            }
+        }
+
+        if tournament.teams.len() < MIN_TEAMS {
+            return Err(invalid());
+        }
+        Ok(tournament)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(home_progress: u32, away_progress: u32, winner: Option<AiSide>) -> MatchResult {
+        MatchResult {
+            home_progress,
+            away_progress,
+            winner,
+        }
+    }
+
+    #[test]
+    fn tournaments_survive_a_round_trip() {
+        let teams = vec!["Ada".to_string(), "Bit".to_string(), "Cog".to_string()];
+        let mut tournament = Tournament::new(Format::Bracket, teams);
+        let first = tournament.next_match().unwrap();
+        tournament.record(first, result(40, 40, None));
+
+        let read = Tournament::parse(&tournament.to_text()).unwrap();
+        assert!(read.format == Format::Bracket);
+        assert_eq!(read.teams, tournament.teams);
+        assert_eq!(read.matches.len(), 2);
+        assert!(read.matches[0]
+            .result
+            .as_ref()
+            .is_some_and(|r| r.winner.is_none()));
+        assert!(read.matches[1].result.is_none());
+        assert_eq!(read.to_text(), tournament.to_text());
+    }
+
+    #[test]
+    fn corrupted_files_are_refused() {
+        let teams = "format\tround-robin\nteam\tAda\nteam\tBit\n";
+        assert!(Tournament::parse(teams).is_ok());
+        assert!(Tournament::parse("format\tround-robin\nteam\tAda\n").is_err());
+        assert!(Tournament::parse(&format!("{}match\t1\t0\t2\t-\n", teams)).is_err());
+        assert!(Tournament::parse(&format!("{}match\t1\t0\tx\t-\n", teams)).is_err());
+        assert!(Tournament::parse(&format!("{}match\t1\t0\t1\tx:3:for\n", teams)).is_err());
+        assert!(Tournament::parse(&format!("{}score\t12\n", teams)).is_err());
+    }
+
+    #[test]
+    fn standings_count_three_points_a_win() {
+        let teams = vec!["Ada".to_string(), "Bit".to_string()];
+        let mut tournament = Tournament::new(Format::RoundRobin, teams);
+        let index = tournament.next_match().unwrap();
+        let home = tournament.matches[index].home;
+        tournament.record(index, result(60, 45, Some(AiSide::For)));
+
+        let standings = tournament.standings();
+        assert_eq!(standings[0].team, home);
+        assert_eq!(standings[0].points(), WIN_POINTS);
+        assert_eq!(standings[0].differential, 15);
+        assert_eq!(standings[1].points(), 0);
+        assert_eq!(tournament.champion(), Some(home));
+    }
+}
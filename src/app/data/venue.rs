@@ -0,0 +1,104 @@
+use super::challenge::{Category, CATEGORIES};
+use crate::config;
+
+// What a challenge needs to be played
+#[derive(Clone, Copy, PartialEq)]
+pub enum Requirement {
+    Water,
+    Paper,
+    JugglingItems,
+    SprintSpace,
+    // A ceiling to touch and a room to leave
+    Indoors,
+}
+
+pub const REQUIREMENTS: &[Requirement] = &[
+    Requirement::Water,
+    Requirement::Paper,
+    Requirement::JugglingItems,
+    Requirement::SprintSpace,
+    Requirement::Indoors,
+];
+
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+pub enum Intensity {
+    Calm,
+    Active,
+    Physical,
+}
+
+impl Requirement {
+    pub fn from_code(code: &str) -> Option<Requirement> {
+        match code {
+            "water" => Some(Requirement::Water),
+            "paper" => Some(Requirement::Paper),
+            "juggling" => Some(Requirement::JugglingItems),
+            "sprint" => Some(Requirement::SprintSpace),
+            "indoors" => Some(Requirement::Indoors),
+            _ => None,
+        }
+    }
+}
+
+impl Intensity {
+    pub fn next(&self) -> Intensity {
+        match self {
+            Intensity::Calm => Intensity::Active,
+            Intensity::Active => Intensity::Physical,
+            Intensity::Physical => Intensity::Calm,
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Intensity> {
+        match code {
+            "calm" => Some(Intensity::Calm),
+            "active" => Some(Intensity::Active),
+            "physical" => Some(Intensity::Physical),
+            _ => None,
+        }
+    }
+}
+
+// Fields of the setup step, the requirements then the intensity
+pub const VENUE_FIELDS: usize = REQUIREMENTS.len() + 1;
+
+// What is available where the game is played
+pub struct Venue {
+    pub available: Vec<Requirement>,
+    // Accessibility, more intense challenges are never drawn
+    pub max_intensity: Intensity,
+    // Challenge packs drawn from
+    pub packs: Vec<Category>,
+}
+
+impl Venue {
+    pub fn new() -> Venue {
+        Venue {
+            available: REQUIREMENTS.to_vec(),
+            max_intensity: Intensity::Physical,
+            packs: config::value("challenge_packs")
+                .and_then(|codes| {
+                    codes
+                        .split(',')
+                        .map(|code| Category::from_code(code.trim()))
+                        .collect::<Option<Vec<Category>>>()
+                })
+                .filter(|packs| !packs.is_empty())
+                .unwrap_or_else(|| CATEGORIES.to_vec()),
+        }
+    }
+
+    pub fn has(&self, requirement: Requirement) -> bool {
+        self.available.contains(&requirement)
+    }
+
+    pub fn toggle(&mut self, field: usize) {
+        match REQUIREMENTS.get(field) {
+            Some(requirement) if self.has(*requirement) => {
+                self.available.retain(|r| r != requirement)
+            }
+            Some(requirement) => self.available.push(*requirement),
+            None => self.max_intensity = self.max_intensity.next(),
+        }
+    }
+}
@@ -1,6 +1,14 @@
-use rand::{rngs::ThreadRng, Rng};
+use rand::{rngs::ThreadRng, seq::SliceRandom};
 
-use super::game::{AiSide, Position, ROW_RANGE};
+use super::{
+    board::{Board, Feature},
+    game::{AiSide, Game, PlacementError, Position, ROW_RANGE},
+};
+
+// Bounds of the VPN rules, cycled during setup
+pub const MAX_VPN_USES: u32 = 3;
+pub const MAX_VPN_COOLDOWN: u32 = 3;
+pub const MAX_VPN_RELOCATE: u32 = 5;
 
 impl AiSide {
     pub fn vpn_column(&self) -> u8 {
@@ -11,9 +19,155 @@ impl AiSide {
     }
 }
 
-pub fn vpn_position(rng: &mut ThreadRng, ai_side: AiSide) -> Position {
-    Position {
-        row: rng.gen_range(ROW_RANGE),
-        col: ai_side.vpn_column(),
+pub fn check_vpn_position(ai_side: &AiSide, position: &Position) -> Result<(), PlacementError> {
+    if position.col != ai_side.vpn_column() {
+        return Err(PlacementError::WrongColumn);
+    }
+    Ok(())
+}
+
+// One teleport, logged for the referee
+pub struct VpnUse {
+    pub ai_side: AiSide,
+    pub round: u32,
+    pub from: Position,
+}
+
+#[derive(PartialEq)]
+pub enum VpnStatus {
+    Ready(u32),
+    // Every use of the round is spent
+    Spent,
+    Cooldown(u32),
+}
+
+pub struct Vpn {
+    pub ai_side: AiSide,
+    pub position: Position,
+    // Since the last relocation
+    pub uses: u32,
+    pub relocations: u32,
+    round_uses: u32,
+    cooldown: u32,
+}
+
+impl Vpn {
+    pub fn new(ai_side: AiSide, position: Position) -> Vpn {
+        Vpn {
+            ai_side,
+            position,
+            uses: 0,
+            relocations: 0,
+            round_uses: 0,
+            cooldown: 0,
+        }
+    }
+
+    pub fn status(&self, max_uses: u32) -> VpnStatus {
+        if self.cooldown > 0 {
+            VpnStatus::Cooldown(self.cooldown)
+        } else if self.round_uses >= max_uses {
+            VpnStatus::Spent
+        } else {
+            VpnStatus::Ready(max_uses - self.round_uses)
+        }
+    }
+
+    // Another row of the same column, away from the occupied cells
+    fn relocate(&mut self, rng: &mut ThreadRng, occupied: &[Position]) {
+        let rows: Vec<Position> = ROW_RANGE
+            .map(|row| Position {
+                row,
+                ..self.position
+            })
+            .filter(|position| *position != self.position && !occupied.contains(position))
+            .collect();
+        if let Some(position) = rows.choose(rng) {
+            self.position = *position;
+        }
+        self.uses = 0;
+        self.relocations += 1;
+    }
+}
+
+impl Game {
+    pub fn vpn(&self, ai_side: &AiSide) -> Option<&Vpn> {
+        self.vpns.iter().find(|vpn| vpn.ai_side == *ai_side)
+    }
+
+    fn vpn_mut(&mut self, ai_side: &AiSide) -> Option<&mut Vpn> {
+        self.vpns.iter_mut().find(|vpn| vpn.ai_side == *ai_side)
+    }
+
+    pub fn place_vpns(&mut self, board: &Board) {
+        self.vpns = [AiSide::For, AiSide::Against]
+            .into_iter()
+            .filter_map(|ai_side| {
+                let position = board.get(&Feature::Vpn(ai_side.clone()))?;
+                Some(Vpn::new(ai_side, position))
+            })
+            .collect();
+    }
+
+    // Capture points a VPN cannot move onto
+    fn occupied(&self) -> Vec<Position> {
+        self.captures
+            .iter()
+            .filter_map(|point| point.position)
+            .collect()
+    }
+
+    // Current positions, (For AI, Against AI)
+    pub fn vpn_positions(&self) -> Option<(Position, Position)> {
+        Some((
+            self.vpn(&AiSide::For)?.position,
+            self.vpn(&AiSide::Against)?.position,
+        ))
+    }
+
+    // Logs a teleport unless the rules forbid it, moving the VPN once worn out
+    pub fn use_vpn(&mut self, ai_side: AiSide) {
+        let (max_uses, relocate) = (self.rules.vpn_uses, self.rules.vpn_relocate);
+        let side = match ai_side {
+            AiSide::For => &self.for_ai,
+            AiSide::Against => &self.against_ai,
+        };
+        let round = side.as_ref().map_or(0, |side| side.nb_rounds + 1);
+        let occupied = self.occupied();
+        let vpn = match self.vpn_mut(&ai_side) {
+            Some(vpn) if matches!(vpn.status(max_uses), VpnStatus::Ready(_)) => vpn,
+            _ => return,
+        };
+        let from = vpn.position;
+        vpn.uses += 1;
+        vpn.round_uses += 1;
+        if relocate > 0 && vpn.uses >= relocate {
+            vpn.relocate(&mut rand::thread_rng(), &occupied);
+        }
+        self.vpn_log.push(VpnUse {
+            ai_side,
+            round,
+            from,
+        });
+    }
+
+    // Called when ai_side ends its turn, a round with teleports starts the cooldown
+    pub fn end_vpn_turn(&mut self, ai_side: &AiSide) {
+        let cooldown = self.rules.vpn_cooldown;
+        if let Some(vpn) = self.vpn_mut(ai_side) {
+            vpn.cooldown = if vpn.round_uses > 0 {
+                cooldown
+            } else {
+                vpn.cooldown.saturating_sub(1)
+            };
+            vpn.round_uses = 0;
+        }
+    }
+
+    pub fn relocate_vpn(&mut self, ai_side: &AiSide) -> Option<Position> {
+        let occupied = self.occupied();
+        let vpn = self.vpn_mut(ai_side)?;
+        vpn.relocate(&mut rand::thread_rng(), &occupied);
+        Some(vpn.position)
     }
 }
@@ -0,0 +1,125 @@
+use std::{error::Error, thread, time::Duration};
+
+use super::{
+    data::{
+        game::{AiSide, Outcome},
+        history::{Filter, History, Stats},
+        rules::GameMode,
+    },
+    i18n::tr,
+};
+
+const HISTORY_USAGE: &str = "\
+usage: cyberconnect history [list|stats] [--name TEXT] [--result for|against|draw] [--mode target|rounds|timed]
+       cyberconnect history show ID
+       cyberconnect history delete ID";
+
+fn filter(args: &[String]) -> Result<Filter, Box<dyn Error>> {
+    let mut filter = Filter::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or(HISTORY_USAGE)?;
+        match arg.as_str() {
+            "--name" => filter.name = value.clone(),
+            "--result" => {
+                filter.outcome = Some(match value.as_str() {
+                    "for" => Outcome::Won(AiSide::For),
+                    "against" => Outcome::Won(AiSide::Against),
+                    "draw" => Outcome::Level,
+                    _ => return Err(HISTORY_USAGE.into()),
+                })
+            }
+            "--mode" => filter.mode = Some(GameMode::from_key(value).ok_or(HISTORY_USAGE)?),
+            _ => return Err(HISTORY_USAGE.into()),
+        }
+    }
+    Ok(filter)
+}
+
+fn id(args: &[String]) -> Result<u32, Box<dyn Error>> {
+    match args {
+        [id] => Ok(id
+            .trim_start_matches('#')
+            .parse()
+            .map_err(|_| HISTORY_USAGE)?),
+        _ => Err(HISTORY_USAGE.into()),
+    }
+}
+
+// Lists, inspects or deletes archived games without starting the interface
+pub fn command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut history = History::load()?;
+    let (action, args) = match args.split_first() {
+        Some((action, args)) if !action.starts_with("--") => (action.as_str(), args),
+        _ => ("list", args),
+    };
+    match action {
+        "list" => {
+            let games = history.filtered(&filter(args)?);
+            if games.is_empty() {
+                println!("{}", tr!("history.empty"));
+            }
+            for record in games {
+                println!("{}", record.summary());
+            }
+        }
+        "stats" => {
+            let games = history.filtered(&filter(args)?);
+            for line in Stats::new(&games).lines() {
+                println!("{}", line);
+            }
+        }
+        "show" => {
+            let id = id(args)?;
+            let record = history.get(id).ok_or(tr!("history.not_found", id))?;
+            for line in record.details() {
+                println!("{}", line);
+            }
+        }
+        "delete" => {
+            let id = id(args)?;
+            if !history.remove(id) {
+                return Err(tr!("history.not_found", id).into());
+            }
+            history.save()?;
+        }
+        _ => return Err(HISTORY_USAGE.into()),
+    }
+    Ok(())
+}
+
+// Archived game turn by turn: the progress at every end of turn, then what moved it
+pub fn replay(id: u32, delay: Duration) -> Result<(), Box<dyn Error>> {
+    let history = History::load()?;
+    let record = history.get(id).ok_or(tr!("history.not_found", id))?;
+
+    println!("{}", record.summary());
+    for (turn, snapshot) in record.timeline.iter().enumerate() {
+        thread::sleep(delay);
+        println!(
+            "{} {:<3}  {} {:>3} - {:<3} {}",
+            tr!("replay.turn"),
+            turn,
+            AiSide::For.name(),
+            snapshot.for_ai,
+            snapshot.against_ai,
+            AiSide::Against.name()
+        );
+        for score in record.scores.iter().filter(|score| score.turn == turn) {
+            println!(
+                "    {} {:+}  {}",
+                score.source.name(),
+                score.points,
+                score.ai_side.name()
+            );
+        }
+        for moment in &snapshot.moments {
+            let team = moment
+                .team()
+                .map_or(tr!("replay.nobody"), |ai_side| ai_side.name());
+            println!("    • {}  {}", moment.name(), team);
+        }
+    }
+    println!("{}", record.result());
+    Ok(())
+}
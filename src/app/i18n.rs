@@ -0,0 +1,110 @@
+mod en;
+mod fr;
+
+use std::{
+    env,
+    fmt::Display,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use crate::config;
+
+// Checked before the config file and the system locale
+pub const LANG_ENV: &str = "CYBERCONNECT_LANG";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Lang {
+    En,
+    Fr,
+}
+
+static LANG: AtomicU8 = AtomicU8::new(Lang::En as u8);
+
+impl Lang {
+    // Accepts plain codes ("fr") as well as locales ("fr_CH.UTF-8")
+    pub fn from_code(code: &str) -> Option<Lang> {
+        match code.get(..2)?.to_lowercase().as_str() {
+            "en" => Some(Lang::En),
+            "fr" => Some(Lang::Fr),
+            _ => None,
+        }
+    }
+
+    fn catalog(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Lang::En => en::CATALOG,
+            Lang::Fr => fr::CATALOG,
+        }
+    }
+}
+
+pub fn set_lang(lang: Lang) {
+    LANG.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn lang() -> Lang {
+    match LANG.load(Ordering::Relaxed) {
+        l if l == Lang::Fr as u8 => Lang::Fr,
+        _ => Lang::En,
+    }
+}
+
+// CLI flag, then environment, then config file, then system locale
+pub fn init(cli: Option<&str>) {
+    let lang = cli
+        .and_then(Lang::from_code)
+        .or_else(|| env::var(LANG_ENV).ok().as_deref().and_then(Lang::from_code))
+        .or_else(|| config::value("lang").as_deref().and_then(Lang::from_code))
+        .or_else(|| env::var("LANG").ok().as_deref().and_then(Lang::from_code))
+        .unwrap_or(Lang::En);
+    set_lang(lang);
+}
+
+fn find(lang: Lang, key: &str) -> Option<&'static str> {
+    lang.catalog()
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, text)| *text)
+}
+
+// Falls back to English, then to the key itself
+pub fn lookup(key: &'static str) -> &'static str {
+    try_lookup(key).unwrap_or(key)
+}
+
+// Same without falling back to the key, for keys built at runtime
+pub fn try_lookup(key: &str) -> Option<&'static str> {
+    find(lang(), key).or_else(|| find(Lang::En, key))
+}
+
+// Replaces each "{}" of the template with the next argument
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut parts = template.split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (i, part) in parts.enumerate() {
+        if let Some(arg) = args.get(i) {
+            text += &arg.to_string();
+        }
+        text += part;
+    }
+    text
+}
+
+// Text before and after the first "{}", to style the argument on its own
+pub fn split(template: &str) -> (&str, &str) {
+    template.split_once("{}").unwrap_or((template, ""))
+}
+
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::app::i18n::lookup($key)
+    };
+    ($key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::app::i18n::fill(
+            $crate::app::i18n::lookup($key),
+            &[$(&$arg as &dyn std::fmt::Display),+],
+        )
+    };
+}
+
+pub(crate) use tr;
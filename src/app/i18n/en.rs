@@ -0,0 +1,375 @@
+pub const CATALOG: &[(&str, &str)] = &[
+    ("title.welcome", "Welcome to CyberConnect!    Fight against the other team to control the AI \"Lucy\".    You have many funny mechanics to discover along the way :)    Have Fun !"),
+    ("side.for", "For AI"),
+    ("side.against", "Against AI"),
+    ("side.nb_rounds", "Number of rounds:"),
+    ("side.points", "Points (progress):"),
+    ("mode.target", "First to {} points"),
+    ("mode.rounds", "{} rounds, highest progress wins"),
+    ("mode.timed", "{} minutes, highest progress wins"),
+    // Key hints
+    ("hint.adjust", "Adjust"),
+    ("hint.back", "Back"),
+    ("hint.balance", "Balance teams"),
+    ("hint.change_format", "Change format"),
+    ("hint.change_mode", "Change mode"),
+    ("hint.delete", "Delete"),
+    ("hint.details", "Details / stats"),
+    ("hint.add_team", "Add team / start"),
+    ("hint.leaderboard", "Leaderboard"),
+    ("hint.history", "History"),
+    ("hint.new_tournament", "New tournament"),
+    ("hint.next", "Next"),
+    ("hint.pick_position", "Pick on the board"),
+    ("hint.place", "Place (empty: unknown position)"),
+    ("hint.erase", "Erase / back"),
+    ("hint.type_position", "Position"),
+    ("hint.confirm", "Confirm"),
+    ("hint.select_item", "Select"),
+    ("hint.reroll", "Re-roll"),
+    ("hint.enter_position", "Type"),
+    ("hint.enter_code", "Enter a code"),
+    ("hint.type_code", "Code"),
+    ("hint.export", "Export sheet"),
+    ("hint.play_next", "Play next match"),
+    ("hint.play_again", "Play again with same teams"),
+    ("hint.quit", "Quit"),
+    ("hint.quit_saved", "Quit (progress is saved)"),
+    ("hint.reset", "Reset"),
+    ("hint.theme", "Theme: {}"),
+    ("hint.toggle", "Toggle"),
+    ("hint.scroll", "Scroll"),
+    ("hint.select", "Select"),
+    ("hint.select_player", "Select player"),
+    ("hint.sudden_death", "Sudden death"),
+    ("hint.suggest", "Suggest"),
+    ("hint.tournament", "Tournament mode"),
+    // Setup
+    ("setup.nb_players", "Number of players:"),
+    ("setup.digit", "Enter digit between {} and {}"),
+    ("setup.press_next", "Press Enter for next"),
+    ("setup.players", "Players"),
+    ("setup.rating_gap", "Rating gap: {}"),
+    ("setup.suggested", "Suggested {} team: {}"),
+    ("setup.name_players", "Name every player to rate this game (optional)"),
+    ("setup.game_mode", "Game mode"),
+    ("setup.mode", "Mode:"),
+    ("setup.sudden_death", "Sudden death:"),
+    ("setup.on", "On"),
+    ("setup.off", "Off"),
+    ("setup.tie_rule", "Tied votes:"),
+    ("setup.races", "Races:"),
+    ("setup.max_races", "At once:"),
+    ("setup.lucy_events", "Lucy events:"),
+    ("setup.capture_transfer", "Captures:"),
+    ("setup.database_supply", "Databases per team:"),
+    ("setup.raid", "Raids:"),
+    ("setup.venue", "Venue"),
+    ("setup.max_intensity", "Max intensity:"),
+    ("setup.packs", "Challenge packs:"),
+    ("setup.playable", "{} challenges can be drawn"),
+    ("setup.handicaps", "Handicaps"),
+    ("setup.start_progress", "Start progress:"),
+    ("setup.start_captures", "Start captures:"),
+    ("setup.capture_points", "Capture points:"),
+    ("setup.vpn", "VPN"),
+    ("setup.vpn_positions", "VPN positions:"),
+    ("setup.generate_vpn", "Press Enter to generate the board"),
+    ("setup.vpn_uses", "Uses per round:"),
+    ("setup.vpn_cooldown", "Cooldown rounds:"),
+    ("setup.vpn_relocate", "Moves after:"),
+    ("setup.never", "Never"),
+    ("setup.spacing", "Spacing"),
+    ("setup.symmetric", "Symmetric"),
+    ("setup.center_reach", "Center reach"),
+    ("setup.new_board", "New board"),
+    ("setup.code", "Setup code:"),
+    ("setup.captures", "Captures"),
+    ("setup.capture_positions", "Capture point positions:"),
+    // Venue
+    ("venue.water", "Water"),
+    ("venue.paper", "Paper"),
+    ("venue.juggling_items", "Juggling items"),
+    ("venue.sprint_space", "Sprint space"),
+    ("venue.indoors", "Indoors"),
+    ("venue.calm", "Calm"),
+    ("venue.active", "Active"),
+    ("venue.physical", "Physical"),
+    ("venue.duels", "Duels"),
+    ("venue.team_challenges", "Team challenges"),
+    // Play
+    ("progress.title", "Progress"),
+    ("progress.target", "First team to {} points wins"),
+    ("progress.round", "Round {} / {}"),
+    ("progress.time_left", "Time left {}"),
+    ("capture.title", "Captures"),
+    ("capture.team", "{} Captures"),
+    ("capture.center", "Center"),
+    ("capture.frozen", "frozen"),
+    ("capture.contested", "contested"),
+    ("race.title", "Race"),
+    ("race.rush", "Rush now to {} ! First team there gains {} points."),
+    ("race.none", "No race for the moment"),
+    ("race.fairness", "Fair {}% ({}/{})"),
+    ("race.recent", "Recent races:"),
+    ("race.won", "{} +{} in {}"),
+    ("race.missed", "missed, -{} each"),
+    ("keys.title", "Keys"),
+    ("keys.exit", "Exit game"),
+    ("keys.end_turn", "End turn"),
+    ("keys.challenge", "Start a challenge (Get ready!)"),
+    ("keys.history", "Challenges played"),
+    ("keys.race_for", "For AI team won race"),
+    ("keys.race_against", "Against AI team won race"),
+    ("keys.center_add_for", "Increase center capture for AI team"),
+    ("keys.center_remove_for", "Decrease center capture for AI team"),
+    ("keys.center_add_against", "Increase center capture for non-AI team"),
+    ("keys.center_remove_against", "Decrease center capture for non-AI team"),
+    ("keys.vpn", "VPN teleport For/Against"),
+    ("keys.retreat_for", "Decrease points for AI team"),
+    ("keys.retreat_against", "Decrease points for non-AI team"),
+    ("keys.capture_add_for", "Increase capture for AI team"),
+    ("keys.capture_remove_for", "Decrease capture for AI team"),
+    ("keys.capture_add_against", "Increase capture for non-AI team"),
+    ("keys.capture_remove_against", "Decrease capture for non-AI team"),
+    ("keys.capture_other", "Other team on a team capture"),
+    ("keys.database_for", "For AI team placed Database"),
+    ("keys.database_against", "Against AI team placed Database"),
+    ("keys.supply", "({} left)"),
+    // Challenge
+    ("challenge.title", "Challenge !"),
+    ("challenge.description", "Description"),
+    ("challenge.intro", "You have to do the following challenge (either as a team or in a duel)."),
+    ("challenge.reward", "The winning team receives {} points !"),
+    ("challenge.sudden_death", "Sudden death! The winning team wins the game !"),
+    ("challenge.cancel", "Cancel Challenge"),
+    ("challenge.change", "Change Challenge"),
+    ("challenge.reroll", "New pick"),
+    ("challenge.won", "Team {} won"),
+    ("challenge.timer", "Timer"),
+    ("challenge.no_timer", "No timer"),
+    ("challenge.time_up", "Time's up!"),
+    ("challenge.start_timer", "Start"),
+    ("challenge.stop_timer", "Stop"),
+    ("challenge.draw", "Draw ({} points each)"),
+    ("challenge.draw_replay", "Draw, play another one"),
+    ("challenge.both_failed", "Both failed"),
+    ("challenge.more_points", "More points"),
+    ("challenge.less_points", "Less points"),
+    ("challenge.ban", "Ban"),
+    ("challenge.judge", "Judges vote ({})"),
+    ("challenge.ballot", "Judge {} of {}, vote without showing the others"),
+    ("challenge.abstain", "Abstain"),
+    ("challenge.cancel_vote", "Cancel vote"),
+    ("challenge.tied_vote", "Tied vote {} - {}, the referee decides"),
+    // Challenges played
+    ("played.title", "Challenges played"),
+    ("played.challenge", "Challenge"),
+    ("played.winner", "Result"),
+    ("played.draw", "Draw"),
+    ("played.both_failed", "Both failed"),
+    ("played.cancelled", "Cancelled"),
+    ("played.empty", "No challenge played yet."),
+    ("played.banned", "Banned: {}"),
+    // Finished
+    ("finished.end", "The END"),
+    ("finished.won", "Well done! The {} team won the game!"),
+    ("finished.draw", "It's a draw! Lucy stays undecided..."),
+    ("finished.thanks", "Thanks for playing, we hope you enjoyed"),
+    ("finished.record", "Press [Enter] to record the result in the tournament."),
+    ("finished.credits", "Credits"),
+    // Tournament
+    ("tournament.title", "Tournament"),
+    ("tournament.format", "Format:"),
+    ("tournament.round_robin", "Round-robin"),
+    ("tournament.bracket", "Bracket (single elimination)"),
+    ("tournament.new_team", "New team"),
+    ("tournament.team_name", "Team name:"),
+    ("tournament.teams", "Teams ({}/{})"),
+    ("tournament.min_teams", "(at least {} teams)"),
+    ("tournament.load_error", "Saved tournament unreadable ({}), left untouched, nothing is saved"),
+    ("tournament.save_error", "Could not save the tournament: {}"),
+    ("tournament.standings", "Standings - {}"),
+    ("tournament.team", "Team"),
+    ("tournament.played", "Played"),
+    ("tournament.wins", "W"),
+    ("tournament.draws", "D"),
+    ("tournament.losses", "L"),
+    ("tournament.points", "Pts"),
+    ("tournament.round", "Round {}"),
+    ("tournament.next_match", "Next match:"),
+    ("tournament.versus", "vs"),
+    ("tournament.champion", "Champion:"),
+    // Leaderboard
+    ("leaderboard.title", "Leaderboard"),
+    ("leaderboard.player", "Player"),
+    ("leaderboard.rating", "Rating"),
+    ("leaderboard.games", "Games"),
+    ("leaderboard.wins", "Wins"),
+    ("leaderboard.win_rate", "Win rate"),
+    ("leaderboard.empty", "No rated player yet, name every player during setup to rate them."),
+    ("leaderboard.error", "Could not access the players file: {}"),
+    ("leaderboard.unreadable", "Players file unreadable ({}), it is left untouched and ratings are not saved"),
+    // Challenges
+    ("duel.shifumi", "[DUEL] Rock paper scissors"),
+    ("duel.one_foot", "[DUEL] Stand on one foot the longest"),
+    ("duel.jump", "[DUEL] Whoever jumps the highest"),
+    ("duel.paper_plane", "[DUEL] Whoever makes the best paper plane (needs paper)"),
+    ("duel.find_object", "[DUEL] First player to find an object that is {pink|hairy|round|made of metal|made of wood|transparent|soft|smaller than a coin}"),
+    ("duel.thumb_war", "[DUEL] Thumb war"),
+    ("duel.name_things", "[DUEL] Take turns naming {@categories}. The first one who can't find one within 5 seconds loses"),
+    ("duel.water", "[DUEL] First to finish a glass of water (needs water)"),
+    ("duel.tongue_twister", "[DUEL] The first player to stumble on \"panier piano\" loses"),
+    ("duel.juggle", "[DUEL] The player who juggles the longest wins (needs things to juggle)"),
+    ("duel.coin_flip", "[DUEL] Heads or tails"),
+    ("duel.draw", "[DUEL] Whoever draws the best {@drawings} in 30s"),
+    ("duel.touch_foot", "[DUEL] First to touch the other player's foot"),
+    ("duel.staring", "[DUEL] Staring contest"),
+    ("duel.count_tiles", "[DUEL] The first player to correctly count the tiles laid on the board wins"),
+    ("duel.sprint", "[DUEL] Whoever runs the fastest in a sprint"),
+    ("duel.push_ups", "[DUEL] Whoever does the most push-ups"),
+    ("duel.impression", "[DUEL] Whoever does the best celebrity impression"),
+    ("team.socks", "[TEAM] The team that takes its socks off the fastest"),
+    ("team.tower", "[TEAM] The team that builds the highest tower in 1 minute"),
+    ("team.name_three", "[TEAM] The first team to name 3 {@categories}"),
+    ("team.raise_limb", "[TEAM] The first team where everyone raises a limb (arms, legs)"),
+    ("team.ceiling", "[TEAM] The first team where everyone touches the ceiling"),
+    ("team.leave_room", "[TEAM] The first team to completely leave the room"),
+    ("team.off_ground", "[TEAM] The first team to stop touching the ground"),
+    ("team.circle", "[TEAM] The first team to form a circle holding arms"),
+    ("team.knees", "[TEAM] The first team where everyone touches their knees"),
+    ("team.poem", "[TEAM] The first team to recite the first two lines of a poem"),
+    ("team.letters", "[TEAM] Each team picks 3 different letters of the alphabet, the team that finds the most different words in 1 minute wins"),
+    // Tied votes
+    ("tie.draw", "Draw"),
+    ("tie.referee", "Referee decides"),
+    ("tie.underdog", "Team behind wins"),
+    // Race placement
+    ("balance.off", "Anywhere"),
+    ("balance.manhattan", "Balanced (straight distance)"),
+    ("balance.path", "Balanced (walking distance)"),
+    // Lucy events
+    ("event.banner", "LUCY: {} - rounds left: {}"),
+    ("event.firewall", "Firewall! The {} capture point is frozen"),
+    ("event.blackout", "Blackout! Scores are hidden"),
+    ("event.overclock", "Overclock! Captures score double"),
+    ("event.teleport", "VPN hijack! The {} team must teleport a player to its VPN"),
+    ("event.teleport_to", "VPN hijack! The {} team must teleport a player to its VPN in {}"),
+    ("event.vpn_move", "VPN glitch! The {} team VPN jumped to {}"),
+    ("event.data_leak", "Data leak! The {} team steals a database ({} points)"),
+    // Lucy event rates
+    ("rate.off", "Off"),
+    ("rate.rare", "Rare"),
+    ("rate.normal", "Normal"),
+    ("rate.frequent", "Frequent"),
+    // Capture transfer
+    ("transfer.majority", "Majority"),
+    ("transfer.exclusive", "Exclusive (until alone)"),
+    // VPN
+    ("vpn.label", "{} VPN {}:"),
+    ("vpn.ready", "ready ({} left)"),
+    ("vpn.spent", "used this round"),
+    ("vpn.cooldown", "cooldown: {}"),
+    ("vpn.uses", "teleports: {}"),
+    ("vpn.last", ", last from {} in round {}"),
+    ("vpn.moves_in", ", moves in {}"),
+    // Databases
+    ("database.title", "{} database"),
+    ("database.prompt", "Where did the {} team place its database? ({} left)"),
+    ("database.position", "Position:"),
+    ("database.placed", "Placed:"),
+    ("database.raid", "Entering a position of the other team raids its database ({})"),
+    ("database.no_supply", "No database left for this team"),
+    ("database.taken", "There is already a database there"),
+    ("raid.off", "Not allowed"),
+    ("raid.steal", "Steal"),
+    ("raid.destroy", "Destroy"),
+    ("position.format", "Enter a column letter and a row number, like C4"),
+    ("position.off_board", "This position is not on the board"),
+    ("placement.illegal", "Nothing can stand on a base or in the middle"),
+    ("placement.wrong_half", "A capture point stays on its team's half, away from the middle columns"),
+    ("placement.wrong_column", "A VPN stays on its team's column (C or I)"),
+    ("placement.taken", "Something already stands there"),
+    ("placement.no_room", "No board fits these constraints"),
+    ("placement.code", "Not a setup code"),
+    // Setup sheet
+    ("sheet.title", "CyberConnect setup sheet"),
+    ("sheet.code", "Setup code: {}"),
+    ("sheet.legend", "BF/BA: bases, VF/VA: VPNs, CF/CA: capture points, CC: center point, ##: middle walls (F: For AI, A: Against AI)"),
+    ("sheet.teams", "Teams"),
+    ("sheet.rules", "Rules"),
+    ("sheet.saved", "Setup sheet saved to {}"),
+    ("sheet.error", "Setup sheet not saved: {}"),
+    // Progress over time
+    ("timeline.title", "Progress over time"),
+    ("timeline.turns", "Turns"),
+    ("timeline.races", "Races"),
+    ("timeline.challenges", "Challenges"),
+    ("timeline.databases", "Databases"),
+    // Game report
+    ("report.title", "Game report"),
+    ("report.duration", "Duration:"),
+    ("report.source", "Points from"),
+    ("report.handicap", "Handicap"),
+    ("report.captures", "Captures"),
+    ("report.center", "Center"),
+    ("report.races", "Races"),
+    ("report.databases", "Databases"),
+    ("report.challenges", "Challenges"),
+    ("report.events", "Lucy events"),
+    ("report.penalties", "Penalties"),
+    ("report.total", "Total"),
+    ("report.largest_lead", "Largest lead:"),
+    ("report.lead", "+{} (turn {})"),
+    ("report.no_lead", "Nobody ever took the lead"),
+    ("report.lead_changes", "Lead changed hands at turns {}"),
+    ("report.no_lead_change", "The lead never changed hands"),
+    ("report.comeback", "came back from {} points behind"),
+    ("report.no_comeback", "No comeback this time"),
+    ("report.challenge_record", "Challenges:"),
+    ("report.undecided", "Undecided {}"),
+    // Match history
+    ("history.title", "Past games"),
+    ("history.filter", "Filter"),
+    ("history.name", "Team or player:"),
+    ("history.any", "any"),
+    ("history.result", "Result"),
+    ("history.result_label", "Result:"),
+    ("history.all", "All"),
+    ("history.won", "{} won"),
+    ("history.draw", "Draw"),
+    ("history.played", "Played"),
+    ("history.score", "Score"),
+    ("history.duration", "Duration"),
+    ("history.mode", "Mode"),
+    ("history.players", "{} player(s)"),
+    ("history.progress", "Progress"),
+    ("history.details", "Game details"),
+    ("history.stats", "Stats"),
+    ("history.points", "Points:"),
+    ("history.games", "Games:"),
+    ("history.wins", "{} wins:"),
+    ("history.draws", "Draws:"),
+    ("history.average_duration", "Average duration:"),
+    ("history.average_turns", "Average turns:"),
+    ("history.comeback", "Biggest comeback: {} points behind (game #{})"),
+    ("history.top_players", "Most wins:"),
+    ("history.empty", "No games yet, finished games are archived here."),
+    ("history.confirm_delete", "Press [Del] again to delete game #{}"),
+    ("history.not_found", "No game #{}"),
+    ("history.seed", "Seed:"),
+    ("history.error", "History file: {}"),
+    ("history.unreadable", "History file unreadable ({}), it is left untouched and nothing is saved"),
+    // Command line
+    ("replay.turn", "Turn"),
+    ("replay.nobody", "nobody"),
+    ("simulate.not_archived", "Simulated games are not archived."),
+    // Themes
+    ("theme.default", "Default"),
+    ("theme.high_contrast", "High contrast"),
+    ("theme.deuteranopia", "Color blind (deuteranopia)"),
+    ("theme.monochrome", "Monochrome"),
+    // Word lists of the challenge templates, options separated by '|'
+    ("words.categories", "car brands|Pokémon|countries|cheeses|football players|green animals|winter sports|actors|capital cities|fruits"),
+    ("words.drawings", "cat|fruit|bike|dragon|robot|castle|dinosaur|burger"),
+];
@@ -0,0 +1,375 @@
+pub const CATALOG: &[(&str, &str)] = &[
+    ("title.welcome", "Bienvenue dans CyberConnect !    Affrontez l'autre équipe pour contrôler l'IA « Lucy ».    De nombreuses mécaniques amusantes vous attendent :)    Amusez-vous bien !"),
+    ("side.for", "Pour l'IA"),
+    ("side.against", "Contre l'IA"),
+    ("side.nb_rounds", "Nombre de manches :"),
+    ("side.points", "Points (progression) :"),
+    ("mode.target", "Premier à {} points"),
+    ("mode.rounds", "{} manches, la meilleure progression gagne"),
+    ("mode.timed", "{} minutes, la meilleure progression gagne"),
+    // Key hints
+    ("hint.adjust", "Ajuster"),
+    ("hint.back", "Retour"),
+    ("hint.balance", "Équilibrer les équipes"),
+    ("hint.change_format", "Changer de format"),
+    ("hint.change_mode", "Changer de mode"),
+    ("hint.delete", "Supprimer"),
+    ("hint.details", "Détails / statistiques"),
+    ("hint.add_team", "Ajouter une équipe / commencer"),
+    ("hint.leaderboard", "Classement"),
+    ("hint.history", "Historique"),
+    ("hint.new_tournament", "Nouveau tournoi"),
+    ("hint.next", "Suivant"),
+    ("hint.pick_position", "Choisir sur le plateau"),
+    ("hint.place", "Placer (vide : position inconnue)"),
+    ("hint.erase", "Effacer / retour"),
+    ("hint.type_position", "Position"),
+    ("hint.confirm", "Valider"),
+    ("hint.select_item", "Choix"),
+    ("hint.reroll", "Relancer"),
+    ("hint.enter_position", "Saisir"),
+    ("hint.enter_code", "Saisir un code"),
+    ("hint.type_code", "Code"),
+    ("hint.export", "Exporter la fiche"),
+    ("hint.play_next", "Jouer le prochain match"),
+    ("hint.play_again", "Rejouer avec les mêmes équipes"),
+    ("hint.quit", "Quitter"),
+    ("hint.quit_saved", "Quitter (la progression est sauvegardée)"),
+    ("hint.reset", "Réinitialiser"),
+    ("hint.theme", "Thème : {}"),
+    ("hint.toggle", "Activer / désactiver"),
+    ("hint.scroll", "Défiler"),
+    ("hint.select", "Sélectionner"),
+    ("hint.select_player", "Choisir le joueur"),
+    ("hint.sudden_death", "Mort subite"),
+    ("hint.suggest", "Suggérer"),
+    ("hint.tournament", "Mode tournoi"),
+    // Setup
+    ("setup.nb_players", "Nombre de joueurs :"),
+    ("setup.digit", "Entrez un chiffre entre {} et {}"),
+    ("setup.press_next", "Appuyez sur Entrée pour continuer"),
+    ("setup.players", "Joueurs"),
+    ("setup.rating_gap", "Écart Elo : {}"),
+    ("setup.suggested", "Équipe {} suggérée : {}"),
+    ("setup.name_players", "Nommez chaque joueur pour classer cette partie (optionnel)"),
+    ("setup.game_mode", "Mode de jeu"),
+    ("setup.mode", "Mode :"),
+    ("setup.sudden_death", "Mort subite :"),
+    ("setup.on", "Activée"),
+    ("setup.off", "Désactivée"),
+    ("setup.tie_rule", "Égalité au vote :"),
+    ("setup.races", "Courses :"),
+    ("setup.max_races", "En même temps :"),
+    ("setup.lucy_events", "Événements de Lucy :"),
+    ("setup.capture_transfer", "Captures :"),
+    ("setup.database_supply", "Bases de données par équipe :"),
+    ("setup.raid", "Raids :"),
+    ("setup.venue", "Lieu"),
+    ("setup.max_intensity", "Intensité max :"),
+    ("setup.packs", "Packs de défis :"),
+    ("setup.playable", "{} défis peuvent être tirés"),
+    ("setup.handicaps", "Handicaps"),
+    ("setup.start_progress", "Progression initiale :"),
+    ("setup.start_captures", "Captures initiales :"),
+    ("setup.capture_points", "Points de capture :"),
+    ("setup.vpn", "VPN"),
+    ("setup.vpn_positions", "Positions VPN :"),
+    ("setup.generate_vpn", "Appuyez sur Entrée pour générer le plateau"),
+    ("setup.vpn_uses", "Utilisations par tour :"),
+    ("setup.vpn_cooldown", "Tours de recharge :"),
+    ("setup.vpn_relocate", "Bouge après :"),
+    ("setup.never", "Jamais"),
+    ("setup.spacing", "Écart"),
+    ("setup.symmetric", "Symétrie"),
+    ("setup.center_reach", "Accès au centre"),
+    ("setup.new_board", "Nouveau plateau"),
+    ("setup.code", "Code de partie :"),
+    ("setup.captures", "Captures"),
+    ("setup.capture_positions", "Positions des points de capture :"),
+    // Venue
+    ("venue.water", "Eau"),
+    ("venue.paper", "Papier"),
+    ("venue.juggling_items", "Objets à jongler"),
+    ("venue.sprint_space", "Place pour sprinter"),
+    ("venue.indoors", "Intérieur"),
+    ("venue.calm", "Calme"),
+    ("venue.active", "Actif"),
+    ("venue.physical", "Physique"),
+    ("venue.duels", "Duels"),
+    ("venue.team_challenges", "Défis d'équipe"),
+    // Play
+    ("progress.title", "Progression"),
+    ("progress.target", "La première équipe à {} points gagne"),
+    ("progress.round", "Manche {} / {}"),
+    ("progress.time_left", "Temps restant {}"),
+    ("capture.title", "Captures"),
+    ("capture.team", "Captures {}"),
+    ("capture.center", "Centre"),
+    ("capture.frozen", "gelée"),
+    ("capture.contested", "disputé"),
+    ("race.title", "Course"),
+    ("race.rush", "Foncez en {} ! La première équipe arrivée gagne {} points."),
+    ("race.none", "Pas de course pour le moment"),
+    ("race.fairness", "Équité {} % ({}/{})"),
+    ("race.recent", "Dernières courses :"),
+    ("race.won", "{} +{} en {}"),
+    ("race.missed", "manquée, -{} chacune"),
+    ("keys.title", "Touches"),
+    ("keys.exit", "Quitter le jeu"),
+    ("keys.end_turn", "Fin du tour"),
+    ("keys.challenge", "Lancer un défi (préparez-vous !)"),
+    ("keys.history", "Défis joués"),
+    ("keys.race_for", "L'équipe pour l'IA gagne la course"),
+    ("keys.race_against", "L'équipe contre l'IA gagne la course"),
+    ("keys.center_add_for", "Augmenter la capture du centre pour l'IA"),
+    ("keys.center_remove_for", "Diminuer la capture du centre pour l'IA"),
+    ("keys.center_add_against", "Augmenter la capture du centre contre l'IA"),
+    ("keys.center_remove_against", "Diminuer la capture du centre contre l'IA"),
+    ("keys.vpn", "Téléportation VPN Pour/Contre"),
+    ("keys.retreat_for", "Retirer des points à l'équipe pour l'IA"),
+    ("keys.retreat_against", "Retirer des points à l'équipe contre l'IA"),
+    ("keys.capture_add_for", "Augmenter la capture pour l'IA"),
+    ("keys.capture_remove_for", "Diminuer la capture pour l'IA"),
+    ("keys.capture_add_against", "Augmenter la capture contre l'IA"),
+    ("keys.capture_remove_against", "Diminuer la capture contre l'IA"),
+    ("keys.capture_other", "Autre équipe sur une capture d'équipe"),
+    ("keys.database_for", "L'équipe pour l'IA a posé une base de données"),
+    ("keys.database_against", "L'équipe contre l'IA a posé une base de données"),
+    ("keys.supply", "({} restantes)"),
+    // Challenge
+    ("challenge.title", "Défi !"),
+    ("challenge.description", "Description"),
+    ("challenge.intro", "Vous devez relever le défi suivant (en équipe ou en duel)."),
+    ("challenge.reward", "L'équipe gagnante reçoit {} points !"),
+    ("challenge.sudden_death", "Mort subite ! L'équipe gagnante remporte la partie !"),
+    ("challenge.cancel", "Annuler le défi"),
+    ("challenge.change", "Changer de défi"),
+    ("challenge.reroll", "Autre tirage"),
+    ("challenge.won", "L'équipe {} a gagné"),
+    ("challenge.timer", "Chrono"),
+    ("challenge.no_timer", "Pas de chrono"),
+    ("challenge.time_up", "Temps écoulé !"),
+    ("challenge.start_timer", "Démarrer"),
+    ("challenge.stop_timer", "Arrêter"),
+    ("challenge.draw", "Égalité ({} points chacun)"),
+    ("challenge.draw_replay", "Égalité, on en rejoue un"),
+    ("challenge.both_failed", "Échec des deux"),
+    ("challenge.more_points", "Plus de points"),
+    ("challenge.less_points", "Moins de points"),
+    ("challenge.ban", "Bannir"),
+    ("challenge.judge", "Vote des juges ({})"),
+    ("challenge.ballot", "Juge {} sur {}, votez sans montrer aux autres"),
+    ("challenge.abstain", "Abstention"),
+    ("challenge.cancel_vote", "Annuler le vote"),
+    ("challenge.tied_vote", "Égalité {} - {}, l'arbitre tranche"),
+    // Challenges played
+    ("played.title", "Défis joués"),
+    ("played.challenge", "Défi"),
+    ("played.winner", "Résultat"),
+    ("played.draw", "Égalité"),
+    ("played.both_failed", "Échec des deux"),
+    ("played.cancelled", "Annulé"),
+    ("played.empty", "Aucun défi joué pour l'instant."),
+    ("played.banned", "Bannis : {}"),
+    // Finished
+    ("finished.end", "FIN"),
+    ("finished.won", "Bravo ! L'équipe {} a remporté la partie !"),
+    ("finished.draw", "Égalité ! Lucy reste indécise..."),
+    ("finished.thanks", "Merci d'avoir joué, on espère que ça vous a plu"),
+    ("finished.record", "Appuyez sur [Entrée] pour enregistrer le résultat dans le tournoi."),
+    ("finished.credits", "Crédits"),
+    // Tournament
+    ("tournament.title", "Tournoi"),
+    ("tournament.format", "Format :"),
+    ("tournament.round_robin", "Championnat (toutes rondes)"),
+    ("tournament.bracket", "Tableau (élimination directe)"),
+    ("tournament.new_team", "Nouvelle équipe"),
+    ("tournament.team_name", "Nom de l'équipe :"),
+    ("tournament.teams", "Équipes ({}/{})"),
+    ("tournament.min_teams", "(au moins {} équipes)"),
+    ("tournament.load_error", "Tournoi sauvegardé illisible ({}), laissé intact, rien n'est enregistré"),
+    ("tournament.save_error", "Impossible de sauvegarder le tournoi : {}"),
+    ("tournament.standings", "Classement - {}"),
+    ("tournament.team", "Équipe"),
+    ("tournament.played", "Joués"),
+    ("tournament.wins", "V"),
+    ("tournament.draws", "N"),
+    ("tournament.losses", "D"),
+    ("tournament.points", "Pts"),
+    ("tournament.round", "Tour {}"),
+    ("tournament.next_match", "Prochain match :"),
+    ("tournament.versus", "contre"),
+    ("tournament.champion", "Champion :"),
+    // Leaderboard
+    ("leaderboard.title", "Classement"),
+    ("leaderboard.player", "Joueur"),
+    ("leaderboard.rating", "Elo"),
+    ("leaderboard.games", "Parties"),
+    ("leaderboard.wins", "Victoires"),
+    ("leaderboard.win_rate", "% victoires"),
+    ("leaderboard.empty", "Aucun joueur classé, nommez chaque joueur pendant la préparation pour les classer."),
+    ("leaderboard.error", "Impossible d'accéder au fichier des joueurs : {}"),
+    ("leaderboard.unreadable", "Fichier des joueurs illisible ({}), il n'est pas modifié et les classements ne sont pas enregistrés"),
+    // Challenges
+    ("duel.shifumi", "[DUEL] Shifumi"),
+    ("duel.one_foot", "[DUEL] Tenir le plus longtemps sur un pied"),
+    ("duel.jump", "[DUEL] Celui qui saute le plus haut"),
+    ("duel.paper_plane", "[DUEL] Celui qui fait le meilleur avion en papier (nécessite papier)"),
+    ("duel.find_object", "[DUEL] Premier joueur à trouver un objet {rose|à poil|rond|en métal|en bois|transparent|doux|plus petit qu'une pièce}"),
+    ("duel.thumb_war", "[DUEL] Guerre des pouces"),
+    ("duel.name_things", "[DUEL] Citer l'un après l'autre des {@categories}. Le premier a ne pas trouver en 5 secondes perds"),
+    ("duel.water", "[DUEL] Le premier à finir un verre d'eau (nécessite eau)"),
+    ("duel.tongue_twister", "[DUEL] Le premier joueur à prononcer faux «panier piano» perd"),
+    ("duel.juggle", "[DUEL] Le joueur qui arrive à jongler le plus longtemps gagne (nécessite trucs pour jongler)"),
+    ("duel.coin_flip", "[DUEL] Pile ou face"),
+    ("duel.draw", "[DUEL] Celui qui dessine le meilleur {@drawings} en 30s"),
+    ("duel.touch_foot", "[DUEL] Le premier à toucher le pied de l'autre joueur"),
+    ("duel.staring", "[DUEL] Combat de regard"),
+    ("duel.count_tiles", "[DUEL] Le premier joueur a compter juste le nombre de cases posées sur le plateau gagne"),
+    ("duel.sprint", "[DUEL] Celui qui court le plus vite en sprint"),
+    ("duel.push_ups", "[DUEL] Celui qui fait le plus de pompes"),
+    ("duel.impression", "[DUEL] Celui qui imite le mieux une célébrité"),
+    ("team.socks", "[TEAM] L'équipe qui enlève le plus vite ses chaussettes"),
+    ("team.tower", "[TEAM] L'équipe qui construit la plus haute tour en 1 minutes"),
+    ("team.name_three", "[TEAM] La première équipe a citer 3 {@categories}"),
+    ("team.raise_limb", "[TEAM] La première équipe à tous lever un membre de son corps (bras, jambes)"),
+    ("team.ceiling", "[TEAM] La première équipe à tous toucher le plafond"),
+    ("team.leave_room", "[TEAM] La première équipe à entièrement sortir de la pièce"),
+    ("team.off_ground", "[TEAM] La première équipe à ne plus toucher le sol"),
+    ("team.circle", "[TEAM] La première équipe à faire un cercle en se tenant les bras"),
+    ("team.knees", "[TEAM] La première équipe à tous se toucher les genoux"),
+    ("team.poem", "[TEAM] La première équipe à réciter les deux premières lignes d'un poème"),
+    ("team.letters", "[TEAM] Chaque équipe choisit 3 lettres différentes de l'alphabet, l'équipe qui trouve le plus de mots différents en 1 minute gagne"),
+    // Tied votes
+    ("tie.draw", "Égalité"),
+    ("tie.referee", "L'arbitre tranche"),
+    ("tie.underdog", "L'équipe menée gagne"),
+    // Race placement
+    ("balance.off", "N'importe où"),
+    ("balance.manhattan", "Équilibrées (distance à vol d'oiseau)"),
+    ("balance.path", "Équilibrées (distance à pied)"),
+    // Lucy events
+    ("event.banner", "LUCY : {} - tours restants : {}"),
+    ("event.firewall", "Pare-feu ! Le point de capture {} est gelé"),
+    ("event.blackout", "Panne ! Les scores sont cachés"),
+    ("event.overclock", "Surcadençage ! Les captures rapportent double"),
+    ("event.teleport", "VPN piraté ! L'équipe {} doit téléporter un joueur sur son VPN"),
+    ("event.teleport_to", "VPN piraté ! L'équipe {} doit téléporter un joueur sur son VPN en {}"),
+    ("event.vpn_move", "Bug de VPN ! Le VPN de l'équipe {} a sauté en {}"),
+    ("event.data_leak", "Fuite de données ! L'équipe {} vole une base de données ({} points)"),
+    // Lucy event rates
+    ("rate.off", "Désactivés"),
+    ("rate.rare", "Rares"),
+    ("rate.normal", "Normaux"),
+    ("rate.frequent", "Fréquents"),
+    // Capture transfer
+    ("transfer.majority", "Majorité"),
+    ("transfer.exclusive", "Exclusive (jusqu'à être seule)"),
+    // VPN
+    ("vpn.label", "VPN {} {} :"),
+    ("vpn.ready", "prêt ({} restant)"),
+    ("vpn.spent", "utilisé ce tour"),
+    ("vpn.cooldown", "recharge : {}"),
+    ("vpn.uses", "téléportations : {}"),
+    ("vpn.last", ", dernière depuis {} au tour {}"),
+    ("vpn.moves_in", ", bouge dans {}"),
+    // Databases
+    ("database.title", "Base de données {}"),
+    ("database.prompt", "Où l'équipe {} a-t-elle placé sa base de données ? ({} restantes)"),
+    ("database.position", "Position :"),
+    ("database.placed", "Placées :"),
+    ("database.raid", "Entrer une position de l'autre équipe attaque sa base de données ({})"),
+    ("database.no_supply", "Plus de base de données pour cette équipe"),
+    ("database.taken", "Il y a déjà une base de données ici"),
+    ("raid.off", "Interdits"),
+    ("raid.steal", "Voler"),
+    ("raid.destroy", "Détruire"),
+    ("position.format", "Entrez une lettre de colonne et un numéro de ligne, comme C4"),
+    ("position.off_board", "Cette position n'est pas sur le plateau"),
+    ("placement.illegal", "Rien ne peut se trouver sur une base ou au milieu"),
+    ("placement.wrong_half", "Un point de capture reste dans la moitié de son équipe, loin des colonnes du milieu"),
+    ("placement.wrong_column", "Un VPN reste sur la colonne de son équipe (C ou I)"),
+    ("placement.taken", "Quelque chose se trouve déjà ici"),
+    ("placement.no_room", "Aucun plateau ne respecte ces contraintes"),
+    ("placement.code", "Ce n'est pas un code de partie"),
+    // Setup sheet
+    ("sheet.title", "Fiche de mise en place CyberConnect"),
+    ("sheet.code", "Code de partie : {}"),
+    ("sheet.legend", "BF/BA : bases, VF/VA : VPN, CF/CA : points de capture, CC : point central, ## : murs du milieu (F : Pour l'IA, A : Contre l'IA)"),
+    ("sheet.teams", "Équipes"),
+    ("sheet.rules", "Règles"),
+    ("sheet.saved", "Fiche enregistrée dans {}"),
+    ("sheet.error", "Fiche non enregistrée : {}"),
+    // Progress over time
+    ("timeline.title", "Progression au fil des tours"),
+    ("timeline.turns", "Tours"),
+    ("timeline.races", "Courses"),
+    ("timeline.challenges", "Défis"),
+    ("timeline.databases", "Bases de données"),
+    // Game report
+    ("report.title", "Bilan de la partie"),
+    ("report.duration", "Durée :"),
+    ("report.source", "Points venant de"),
+    ("report.handicap", "Handicap"),
+    ("report.captures", "Captures"),
+    ("report.center", "Centre"),
+    ("report.races", "Courses"),
+    ("report.databases", "Bases de données"),
+    ("report.challenges", "Défis"),
+    ("report.events", "Événements de Lucy"),
+    ("report.penalties", "Pénalités"),
+    ("report.total", "Total"),
+    ("report.largest_lead", "Plus grande avance :"),
+    ("report.lead", "+{} (tour {})"),
+    ("report.no_lead", "Personne n'a jamais pris l'avantage"),
+    ("report.lead_changes", "L'avantage a changé de camp aux tours {}"),
+    ("report.no_lead_change", "L'avantage n'a jamais changé de camp"),
+    ("report.comeback", "est revenue de {} points de retard"),
+    ("report.no_comeback", "Pas de remontée cette fois"),
+    ("report.challenge_record", "Défis :"),
+    ("report.undecided", "Indécis {}"),
+    // Match history
+    ("history.title", "Parties jouées"),
+    ("history.filter", "Filtre"),
+    ("history.name", "Équipe ou joueur :"),
+    ("history.any", "tous"),
+    ("history.result", "Résultat"),
+    ("history.result_label", "Résultat :"),
+    ("history.all", "Tous"),
+    ("history.won", "Victoire {}"),
+    ("history.draw", "Égalité"),
+    ("history.played", "Jouée le"),
+    ("history.score", "Score"),
+    ("history.duration", "Durée"),
+    ("history.mode", "Mode"),
+    ("history.players", "{} joueur(s)"),
+    ("history.progress", "Progression"),
+    ("history.details", "Détails de la partie"),
+    ("history.stats", "Statistiques"),
+    ("history.points", "Points :"),
+    ("history.games", "Parties :"),
+    ("history.wins", "Victoires {} :"),
+    ("history.draws", "Égalités :"),
+    ("history.average_duration", "Durée moyenne :"),
+    ("history.average_turns", "Tours en moyenne :"),
+    ("history.comeback", "Plus belle remontée : {} points de retard (partie #{})"),
+    ("history.top_players", "Plus de victoires :"),
+    ("history.empty", "Aucune partie pour l'instant, les parties terminées sont archivées ici."),
+    ("history.confirm_delete", "Appuyez encore sur [Suppr] pour supprimer la partie #{}"),
+    ("history.not_found", "Pas de partie #{}"),
+    ("history.seed", "Graine :"),
+    ("history.error", "Fichier d'historique : {}"),
+    ("history.unreadable", "Fichier d'historique illisible ({}), il n'est pas modifié et rien n'est enregistré"),
+    // Command line
+    ("replay.turn", "Tour"),
+    ("replay.nobody", "personne"),
+    ("simulate.not_archived", "Les parties simulées ne sont pas archivées."),
+    // Themes
+    ("theme.default", "Par défaut"),
+    ("theme.high_contrast", "Contraste élevé"),
+    ("theme.deuteranopia", "Daltonien (deutéranopie)"),
+    ("theme.monochrome", "Monochrome"),
+    // Word lists of the challenge templates, options separated by '|'
+    ("words.categories", "marques de voitures|Pokémon|pays|fromages|joueurs de foot|animaux verts|sports d'hiver|acteurs|capitales|fruits"),
+    ("words.drawings", "chat|fruit|vélo|dragon|robot|château|dinosaure|hamburger"),
+];
@@ -0,0 +1,92 @@
+use crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind};
+use tui::{
+    layout::Rect,
+    text::{Span, Spans},
+};
+
+use super::{App, AppState};
+
+// Clickable areas recorded while drawing, a click acts like the key of the area
+#[derive(Default)]
+pub struct Targets(Vec<(Rect, KeyCode)>);
+
+impl Targets {
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn add(&mut self, rect: Rect, code: KeyCode) {
+        self.0.push((rect, code));
+    }
+
+    // Single line of rect
+    pub fn add_row(&mut self, rect: Rect, row: u16, code: KeyCode) {
+        if row < rect.height {
+            self.add(Rect::new(rect.x, rect.y + row, rect.width, 1), code);
+        }
+    }
+
+    // Line of hints centered in the first row of rect, each hint bound to an optional key
+    pub fn hint_line<'a>(
+        &mut self,
+        rect: Rect,
+        hints: Vec<(Vec<Span<'a>>, Option<KeyCode>)>,
+    ) -> Spans<'a> {
+        let width: usize = hints
+            .iter()
+            .flat_map(|(spans, _)| spans)
+            .map(Span::width)
+            .sum();
+        // Same offset as centered paragraphs
+        let mut x = rect.x + (rect.width / 2).saturating_sub(width as u16 / 2);
+        let mut line = vec![];
+        for (spans, code) in hints {
+            let width = spans.iter().map(Span::width).sum::<usize>() as u16;
+            if let Some(code) = code {
+                self.add(Rect::new(x, rect.y, width, 1), code);
+            }
+            x += width;
+            line.extend(spans);
+        }
+        Spans::from(line)
+    }
+
+    // Last drawn area wins when they overlap
+    fn hit(&self, column: u16, row: u16) -> Option<KeyCode> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(rect, _)| {
+                (rect.left()..rect.right()).contains(&column)
+                    && (rect.top()..rect.bottom()).contains(&row)
+            })
+            .map(|(_, code)| *code)
+    }
+}
+
+impl App<'_> {
+    pub fn on_mouse(&mut self, event: MouseEvent) {
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(code) = self.targets.hit(event.column, event.row) {
+                    self.on_key(code);
+                }
+            }
+            // The wheel only scrolls lists
+            MouseEventKind::ScrollUp | MouseEventKind::ScrollDown => {
+                if let AppState::Leaderboard
+                | AppState::ChallengeHistory
+                | AppState::RosterInput
+                | AppState::Standings
+                | AppState::History = self.state
+                {
+                    self.on_key(match event.kind {
+                        MouseEventKind::ScrollUp => KeyCode::Up,
+                        _ => KeyCode::Down,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use std::{fs, io};
+
+use itertools::Itertools;
+
+use super::{
+    data::{
+        board::{Board, Cell, Feature, FEATURES},
+        capture::Location,
+        game::{AiSide, Game, COL_ALPHABET, COL_RANGE},
+        rules::Rules,
+    },
+    i18n::tr,
+};
+
+// Same sheet as plain text, Markdown and SVG, in one file per extension
+pub const SHEET_FILE: &str = "cyberconnect-setup";
+pub const SHEET_EXTENSIONS: [&str; 3] = ["txt", "md", "svg"];
+
+// SVG layout, in pixels
+const CELL_SIZE: usize = 48;
+const MARGIN: usize = 32;
+const LINE_HEIGHT: usize = 20;
+const CHAR_WIDTH: usize = 9;
+
+// Printable setup to lay out the physical board from, with the code giving the layout back
+pub struct Sheet {
+    code: String,
+    grid: Vec<Vec<Cell>>,
+    positions: Vec<String>,
+    // Title and handicap line of each team
+    teams: Vec<(String, String)>,
+    rules: Vec<String>,
+}
+
+// Two letters per cell, the same in every language
+fn mark(cell: &Cell) -> &'static str {
+    match cell {
+        Cell::Empty => "..",
+        Cell::Wall => "##",
+        Cell::Base(AiSide::For) => "BF",
+        Cell::Base(AiSide::Against) => "BA",
+        Cell::Feature(Feature::Vpn(AiSide::For)) => "VF",
+        Cell::Feature(Feature::Vpn(AiSide::Against)) => "VA",
+        Cell::Feature(Feature::Capture(Location::Home(AiSide::For))) => "CF",
+        Cell::Feature(Feature::Capture(Location::Home(AiSide::Against))) => "CA",
+        Cell::Feature(Feature::Capture(Location::Center)) => "CC",
+    }
+}
+
+fn fill(cell: &Cell) -> &'static str {
+    match cell {
+        Cell::Empty => "#ffffff",
+        Cell::Wall => "#9e9e9e",
+        Cell::Base(AiSide::For) => "#cfe2ff",
+        Cell::Base(AiSide::Against) => "#ffd6d6",
+        Cell::Feature(_) => "#fff3bf",
+    }
+}
+
+fn on_off(on: bool) -> &'static str {
+    if on {
+        tr!("setup.on")
+    } else {
+        tr!("setup.off")
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub(super) fn rule_lines(rules: &Rules) -> Vec<String> {
+    let venue = &rules.venue;
+    vec![
+        format!("{} {}", tr!("setup.mode"), rules.mode.name()),
+        format!(
+            "{} {}",
+            tr!("setup.sudden_death"),
+            on_off(rules.sudden_death)
+        ),
+        format!("{} {}", tr!("setup.tie_rule"), rules.tie_rule.name()),
+        format!(
+            "{} {}    {} {}",
+            tr!("setup.races"),
+            rules.race_balance.name(),
+            tr!("setup.max_races"),
+            rules.max_races
+        ),
+        format!(
+            "{} {}    {} {}",
+            tr!("setup.lucy_events"),
+            rules.event_rate.name(),
+            tr!("setup.capture_transfer"),
+            rules.capture_transfer.name()
+        ),
+        format!(
+            "{} {}    {} {}",
+            tr!("setup.database_supply"),
+            rules.database_supply,
+            tr!("setup.raid"),
+            rules.raid.name()
+        ),
+        format!(
+            "{} {}    {} {}    {} {}",
+            tr!("setup.vpn_uses"),
+            rules.vpn_uses,
+            tr!("setup.vpn_cooldown"),
+            rules.vpn_cooldown,
+            tr!("setup.vpn_relocate"),
+            match rules.vpn_relocate {
+                0 => tr!("setup.never").to_string(),
+                uses => uses.to_string(),
+            }
+        ),
+        format!(
+            "{}: {}    {} {}    {} {}",
+            tr!("setup.venue"),
+            venue.available.iter().map(|r| r.name()).join(", "),
+            tr!("setup.max_intensity"),
+            venue.max_intensity.name(),
+            tr!("setup.packs"),
+            venue.packs.iter().map(|pack| pack.name()).join(", ")
+        ),
+        format!(
+            "{} {}    {} {}    {} {}",
+            tr!("setup.spacing"),
+            rules.board.spacing,
+            tr!("setup.symmetric"),
+            on_off(rules.board.symmetric),
+            tr!("setup.center_reach"),
+            rules.board.center_reach
+        ),
+    ]
+}
+
+impl Sheet {
+    pub fn new(game: &Game, board: &Board) -> Sheet {
+        let position_line = |label: &str, features: &[Feature]| {
+            let positions = features
+                .iter()
+                .map(|feature| {
+                    let name = match feature {
+                        Feature::Vpn(ai_side) | Feature::Capture(Location::Home(ai_side)) => {
+                            ai_side.name()
+                        }
+                        Feature::Capture(Location::Center) => tr!("capture.center"),
+                    };
+                    let position = board
+                        .get(feature)
+                        .map_or("?".to_string(), |position| position.to_string());
+                    format!("{} {}", name, position)
+                })
+                .join("  ");
+            format!("{} {}", label, positions)
+        };
+
+        let teams = [
+            (AiSide::For, &game.for_ai),
+            (AiSide::Against, &game.against_ai),
+        ]
+        .into_iter()
+        .filter_map(|(ai_side, side)| {
+            let side = side.as_ref()?;
+            let title = match game.team_name(&ai_side) {
+                Some(team) => format!("{} - {}", ai_side.name(), team),
+                None => ai_side.name().to_string(),
+            };
+            let players = side
+                .players
+                .iter()
+                .map(|name| match name.trim() {
+                    "" => "?",
+                    name => name,
+                })
+                .join(", ");
+            let handicap = &side.handicap;
+            Some((
+                format!("{}: {}", title, players),
+                format!(
+                    "{} +{}  {} +{}  {} x{:.2}",
+                    tr!("setup.start_progress"),
+                    handicap.progress,
+                    tr!("setup.start_captures"),
+                    handicap.captures,
+                    tr!("setup.capture_points"),
+                    handicap.multiplier
+                ),
+            ))
+        })
+        .collect();
+
+        Sheet {
+            code: board.code(),
+            grid: board.grid(),
+            positions: vec![
+                position_line(tr!("setup.vpn_positions"), &FEATURES[..2]),
+                position_line(tr!("setup.capture_positions"), &FEATURES[2..]),
+            ],
+            teams,
+            rules: rule_lines(&game.rules),
+        }
+    }
+
+    fn columns() -> impl Iterator<Item = char> {
+        COL_ALPHABET.chars().take(*COL_RANGE.end() as usize)
+    }
+
+    pub fn text(&self) -> String {
+        let mut lines = vec![
+            tr!("sheet.title").to_string(),
+            tr!("sheet.code", self.code),
+            String::new(),
+            format!(
+                "    {}",
+                Self::columns().map(|col| format!("{:<4}", col)).join("")
+            ),
+        ];
+        for (row, cells) in self.grid.iter().enumerate() {
+            let cells = cells
+                .iter()
+                .map(|cell| format!("{:<4}", mark(cell)))
+                .join("");
+            lines.push(format!("{:>2}  {}", row + 1, cells));
+        }
+        lines.push(tr!("sheet.legend").to_string());
+        lines.push(String::new());
+        lines.extend(self.positions.iter().cloned());
+        lines.push(String::new());
+        lines.push(tr!("sheet.teams").to_string());
+        for (title, handicap) in &self.teams {
+            lines.push(format!("  {}", title));
+            lines.push(format!("    {}", handicap));
+        }
+        lines.push(String::new());
+        lines.push(tr!("sheet.rules").to_string());
+        lines.extend(self.rules.iter().map(|rule| format!("  {}", rule)));
+        lines
+            .iter()
+            .map(|line| format!("{}\n", line.trim_end()))
+            .collect()
+    }
+
+    pub fn markdown(&self) -> String {
+        let mut lines = vec![
+            format!("# {}", tr!("sheet.title")),
+            String::new(),
+            tr!("sheet.code", format!("`{}`", self.code)),
+            String::new(),
+            format!("| | {} |", Self::columns().join(" | ")),
+            format!("|---|{}", Self::columns().map(|_| "---|").join("")),
+        ];
+        for (row, cells) in self.grid.iter().enumerate() {
+            lines.push(format!(
+                "| {} | {} |",
+                row + 1,
+                cells.iter().map(mark).join(" | ")
+            ));
+        }
+        lines.push(String::new());
+        lines.push(format!("*{}*", tr!("sheet.legend")));
+        lines.push(String::new());
+        lines.extend(self.positions.iter().map(|line| format!("- {}", line)));
+        lines.push(String::new());
+        lines.push(format!("## {}", tr!("sheet.teams")));
+        lines.push(String::new());
+        for (title, handicap) in &self.teams {
+            lines.push(format!("- **{}**  ", title));
+            lines.push(format!("  {}", handicap));
+        }
+        lines.push(String::new());
+        lines.push(format!("## {}", tr!("sheet.rules")));
+        lines.push(String::new());
+        lines.extend(self.rules.iter().map(|rule| format!("- {}", rule)));
+        lines.iter().map(|line| format!("{}\n", line)).collect()
+    }
+
+    pub fn svg(&self) -> String {
+        let cols = *COL_RANGE.end() as usize;
+        let rows = self.grid.len();
+        // Lines under the board
+        let mut notes = vec![tr!("sheet.legend").to_string(), String::new()];
+        notes.extend(self.positions.iter().cloned());
+        notes.push(String::new());
+        notes.push(tr!("sheet.teams").to_string());
+        for (title, handicap) in &self.teams {
+            notes.push(format!("  {}", title));
+            notes.push(format!("    {}", handicap));
+        }
+        notes.push(String::new());
+        notes.push(tr!("sheet.rules").to_string());
+        notes.extend(self.rules.iter().map(|rule| format!("  {}", rule)));
+
+        let board_x = MARGIN + LINE_HEIGHT;
+        let board_y = MARGIN + 3 * LINE_HEIGHT;
+        let notes_y = board_y + rows * CELL_SIZE + 2 * LINE_HEIGHT;
+        let longest = notes
+            .iter()
+            .map(|note| note.chars().count())
+            .max()
+            .unwrap_or(0);
+        let width = (board_x + cols * CELL_SIZE).max(longest * CHAR_WIDTH) + MARGIN;
+        let height = notes_y + notes.len() * LINE_HEIGHT + MARGIN;
+
+        let mut svg = vec![
+            format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="monospace" font-size="14">"#,
+                width, height
+            ),
+            r#"<rect width="100%" height="100%" fill="white"/>"#.to_string(),
+            format!(
+                r#"<text x="{}" y="{}" font-size="20" font-weight="bold">{}</text>"#,
+                MARGIN,
+                MARGIN,
+                escape(tr!("sheet.title"))
+            ),
+            format!(
+                r#"<text x="{}" y="{}">{}</text>"#,
+                MARGIN,
+                MARGIN + LINE_HEIGHT + 4,
+                escape(&tr!("sheet.code", self.code))
+            ),
+        ];
+        for (i, col) in Self::columns().enumerate() {
+            svg.push(format!(
+                r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+                board_x + i * CELL_SIZE + CELL_SIZE / 2,
+                board_y - 8,
+                col
+            ));
+        }
+        for (row, cells) in self.grid.iter().enumerate() {
+            let y = board_y + row * CELL_SIZE;
+            svg.push(format!(
+                r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+                MARGIN,
+                y + CELL_SIZE / 2 + 5,
+                row + 1
+            ));
+            for (col, cell) in cells.iter().enumerate() {
+                let x = board_x + col * CELL_SIZE;
+                svg.push(format!(
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" stroke="black"/>"#,
+                    x,
+                    y,
+                    CELL_SIZE,
+                    CELL_SIZE,
+                    fill(cell)
+                ));
+                if let Cell::Base(_) | Cell::Feature(_) = cell {
+                    svg.push(format!(
+                        r#"<text x="{}" y="{}" text-anchor="middle" font-weight="bold">{}</text>"#,
+                        x + CELL_SIZE / 2,
+                        y + CELL_SIZE / 2 + 5,
+                        mark(cell)
+                    ));
+                }
+            }
+        }
+        for (i, note) in notes.iter().enumerate() {
+            svg.push(format!(
+                r#"<text x="{}" y="{}" xml:space="preserve">{}</text>"#,
+                MARGIN,
+                notes_y + i * LINE_HEIGHT,
+                escape(note)
+            ));
+        }
+        svg.push("</svg>".to_string());
+        svg.iter().map(|line| format!("{}\n", line)).collect()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        for (extension, content) in
+            SHEET_EXTENSIONS
+                .iter()
+                .zip([self.text(), self.markdown(), self.svg()])
+        {
+            fs::write(format!("{}.{}", SHEET_FILE, extension), content)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use std::{cmp::Ordering, error::Error};
+
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    cli::Setup,
+    data::{
+        board::Board,
+        capture::Location,
+        challenge::{ChallengeOutcome, Deck},
+        game::{AiSide, Game, Outcome},
+        history::{Record, Stats},
+        score::Source,
+        side::Side,
+        timeline::Moment,
+    },
+    i18n::tr,
+    sheet::rule_lines,
+};
+
+// Teams of a simulation without --for-players or --against-players
+const DEFAULT_PLAYERS: u8 = 2;
+// Timed games do not end without a clock, the leader wins after that many turns
+const MAX_TURNS: usize = 200;
+
+// Chances of each move of the bots during a turn
+const CAPTURE_PROB: f64 = 0.6;
+const RACE_PROB: f64 = 0.4;
+const CHALLENGE_PROB: f64 = 0.25;
+
+fn random_outcome<R: Rng>(rng: &mut R) -> ChallengeOutcome {
+    match rng.gen_range(0..10) {
+        0 => ChallengeOutcome::Draw,
+        1 => ChallengeOutcome::BothFailed,
+        _ => ChallengeOutcome::Won(rng.gen()),
+    }
+}
+
+// One challenge played by the bots, the winner if any
+fn play_challenge<R: Rng>(game: &mut Game, deck: &mut Deck, rng: &mut R) -> Option<AiSide> {
+    let challenge = deck.draw(game);
+    let outcome = random_outcome(rng);
+    deck.record(&challenge, Some(outcome.clone()), game);
+    let winner = match &outcome {
+        ChallengeOutcome::Won(ai_side) => Some(ai_side.clone()),
+        _ => None,
+    };
+    game.mark(Moment::Challenge(winner.clone()));
+    if !game.sudden_death {
+        let (for_points, against_points) = challenge.rewards(&outcome);
+        game.score(&AiSide::For, Source::Challenges, for_points as i64);
+        game.score(&AiSide::Against, Source::Challenges, against_points as i64);
+    }
+    winner
+}
+
+// Bots playing random moves until the game is over, the winner if any
+fn play<R: Rng>(game: &mut Game, deck: &mut Deck, rng: &mut R) -> Option<AiSide> {
+    let locations = [
+        Location::Home(AiSide::For),
+        Location::Center,
+        Location::Home(AiSide::Against),
+    ];
+    while game.timeline.len() < MAX_TURNS {
+        let ai_side = game.turn.clone();
+        if rng.gen_bool(CAPTURE_PROB) {
+            let location = locations.choose(rng).unwrap();
+            if rng.gen_bool(0.7) {
+                game.add_capture(location, ai_side.clone());
+            } else {
+                game.remove_capture(location, ai_side.clone());
+            }
+        }
+        if !game.races.is_empty() && rng.gen_bool(RACE_PROB) {
+            game.claim_race(0, rng.gen());
+        }
+        if rng.gen_bool(CHALLENGE_PROB) {
+            play_challenge(game, deck, rng);
+        }
+        game.end_turn();
+        if game.outcome().is_some() {
+            break;
+        }
+    }
+
+    let progress = |side: &Option<Side>| side.as_ref().map_or(0, Side::progress);
+    let outcome = game.outcome().unwrap_or_else(|| {
+        match progress(&game.for_ai).cmp(&progress(&game.against_ai)) {
+            Ordering::Greater => Outcome::Won(AiSide::For),
+            Ordering::Less => Outcome::Won(AiSide::Against),
+            Ordering::Equal => Outcome::Level,
+        }
+    });
+    match outcome {
+        Outcome::Won(ai_side) => Some(ai_side),
+        Outcome::Level if game.rules.sudden_death => {
+            game.sudden_death = true;
+            loop {
+                if let Some(ai_side) = play_challenge(game, deck, rng) {
+                    return Some(ai_side);
+                }
+            }
+        }
+        Outcome::Level => None,
+    }
+}
+
+// Games played by bots with the given setup, they are not archived
+pub fn simulate(setup: &Setup, games: u32) -> Result<(), Box<dyn Error>> {
+    let mut rng = rand::thread_rng();
+    let board = setup.board()?;
+    let mut records = vec![];
+
+    for id in 1..=games {
+        let mut game = Game::new();
+        setup.apply(&mut game);
+        for side in [&mut game.for_ai, &mut game.against_ai] {
+            side.get_or_insert_with(|| Side::new(DEFAULT_PLAYERS));
+        }
+        game.suggest_handicaps();
+        let board = match &board {
+            Some(board) => board.clone(),
+            None => Board::generate(&mut game.board_rng, &game.rules.board)
+                .map_err(|err| err.description().to_string())?,
+        };
+        game.place_vpns(&board);
+        game.place_captures(&board);
+        game.begin();
+
+        let mut deck = Deck::default();
+        let winner = play(&mut game, &mut deck, &mut rng);
+        game.finish();
+
+        let mut record = Record::new(&game, winner, Some(board.code()), rule_lines(&game.rules));
+        record.id = id;
+        println!("{}", record.summary());
+        records.push(record);
+    }
+
+    println!();
+    let records: Vec<&Record> = records.iter().collect();
+    for line in Stats::new(&records).lines() {
+        println!("{}", line);
+    }
+    println!("{}", tr!("simulate.not_archived"));
+    Ok(())
+}
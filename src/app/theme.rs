@@ -0,0 +1,142 @@
+use std::{
+    env,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use tui::style::Color;
+
+use crate::config;
+
+pub const THEME_ENV: &str = "CYBERCONNECT_THEME";
+
+pub struct Palette {
+    pub id: &'static str,
+    // i18n key of the displayed name
+    pub name: &'static str,
+    pub for_ai: Color,
+    pub against_ai: Color,
+    // Races, challenges and screen borders
+    pub accent: Color,
+    // Progress, captures and table headers
+    pub highlight: Color,
+    pub key: Color,
+    pub success: Color,
+    pub error: Color,
+    pub text: Color,
+    pub background: Color,
+    // Team marks and gauge fills, in every theme so teams don't rely on color alone
+    pub for_mark: &'static str,
+    pub against_mark: &'static str,
+    pub for_fill: &'static str,
+    pub against_fill: &'static str,
+}
+
+pub const PALETTES: &[Palette] = &[
+    Palette {
+        id: "default",
+        name: "theme.default",
+        for_ai: Color::Cyan,
+        against_ai: Color::Red,
+        accent: Color::Magenta,
+        highlight: Color::Yellow,
+        key: Color::Rgb(138, 138, 138),
+        success: Color::Green,
+        error: Color::Red,
+        text: Color::White,
+        background: Color::Black,
+        for_mark: "▲ ",
+        against_mark: "▼ ",
+        for_fill: "█",
+        against_fill: "▒",
+    },
+    // Saturated colors that survive a washed out projector
+    Palette {
+        id: "high-contrast",
+        name: "theme.high_contrast",
+        for_ai: Color::Rgb(40, 110, 255),
+        against_ai: Color::Rgb(255, 40, 40),
+        accent: Color::Rgb(255, 0, 255),
+        highlight: Color::Rgb(255, 255, 0),
+        key: Color::White,
+        success: Color::Rgb(0, 255, 0),
+        error: Color::Rgb(255, 80, 0),
+        text: Color::White,
+        background: Color::Black,
+        for_mark: "▲ ",
+        against_mark: "▼ ",
+        for_fill: "█",
+        against_fill: "▒",
+    },
+    // Okabe-Ito colors, distinguishable with red/green color blindness
+    Palette {
+        id: "deuteranopia",
+        name: "theme.deuteranopia",
+        for_ai: Color::Rgb(0, 114, 178),
+        against_ai: Color::Rgb(230, 159, 0),
+        accent: Color::Rgb(204, 121, 167),
+        highlight: Color::Rgb(240, 228, 66),
+        key: Color::Rgb(138, 138, 138),
+        success: Color::Rgb(86, 180, 233),
+        error: Color::Rgb(213, 94, 0),
+        text: Color::White,
+        background: Color::Black,
+        for_mark: "▲ ",
+        against_mark: "▼ ",
+        for_fill: "█",
+        against_fill: "▒",
+    },
+    Palette {
+        id: "monochrome",
+        name: "theme.monochrome",
+        for_ai: Color::White,
+        against_ai: Color::Gray,
+        accent: Color::White,
+        highlight: Color::White,
+        key: Color::Gray,
+        success: Color::White,
+        error: Color::White,
+        text: Color::White,
+        background: Color::Black,
+        for_mark: "▲ ",
+        against_mark: "▼ ",
+        for_fill: "█",
+        against_fill: "░",
+    },
+];
+
+static THEME: AtomicUsize = AtomicUsize::new(0);
+
+pub fn theme() -> &'static Palette {
+    &PALETTES[THEME.load(Ordering::Relaxed) % PALETTES.len()]
+}
+
+pub fn set_theme(id: &str) -> bool {
+    match PALETTES.iter().position(|p| p.id == id) {
+        Some(index) => {
+            THEME.store(index, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn next_theme() {
+    THEME.store(
+        (THEME.load(Ordering::Relaxed) + 1) % PALETTES.len(),
+        Ordering::Relaxed,
+    );
+}
+
+// CLI flag, then environment, then config file
+pub fn init(cli: Option<&str>) {
+    let candidates = [
+        cli.map(str::to_string),
+        env::var(THEME_ENV).ok(),
+        config::value("theme"),
+    ];
+    for id in candidates.into_iter().flatten() {
+        if set_theme(&id) {
+            return;
+        }
+    }
+}
@@ -1,27 +1,111 @@
+use crossterm::event::KeyCode;
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
-    text::Span,
+    style::{Modifier, Style},
+    text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::app::data::{capture::Capture, game::Game};
+use crate::app::{
+    data::{
+        capture::{CapturePoint, Location},
+        game::{AiSide, Game},
+    },
+    i18n::tr,
+    mouse::Targets,
+    theme::theme,
+};
+
+// Occupants of both teams, the owner's count is highlighted
+fn occupants_from(point: &CapturePoint) -> Spans<'static> {
+    let count = |ai_side: AiSide| {
+        let style = Style::default().add_modifier(Modifier::BOLD);
+        Span::styled(
+            format!(" {} ", point.occupants(&ai_side)),
+            if point.owner() == Some(&ai_side) {
+                style.bg(ai_side.color()).fg(theme().background)
+            } else {
+                style.fg(ai_side.color())
+            },
+        )
+    };
+    Spans::from(vec![
+        count(AiSide::For),
+        Span::from(" "),
+        count(AiSide::Against),
+    ])
+}
+
+// Remove and add keys of each team, (For AI, Against AI)
+fn keys_of(location: &Location) -> ((char, char), (char, char)) {
+    match location {
+        Location::Home(AiSide::For) => (('u', 'e'), ('U', 'E')),
+        Location::Home(AiSide::Against) => (('H', 'T'), ('h', 't')),
+        Location::Center => (('o', 'a'), ('n', 's')),
+    }
+}
+
+impl Location {
+    pub fn name(&self) -> String {
+        match self {
+            Location::Home(ai_side) => tr!("capture.team", ai_side.label()),
+            Location::Center => tr!("capture.center").to_string(),
+        }
+    }
+}
+
+const BUTTON_WIDTH: u16 = 3;
 
-fn capture_from(capture: &Capture) -> Paragraph {
-    Paragraph::new(Span::styled(
-        format!(" {} ", capture.count().to_string()),
-        Style::default()
-            .add_modifier(Modifier::BOLD)
-            .bg(capture.ai_side.color())
-            .fg(Color::Black),
-    ))
-    .style(Style::default().fg(capture.ai_side.color()))
-    .alignment(Alignment::Center)
+// Clickable buttons on both sides of a centered count, listed from the count outwards
+fn draw_buttons<B: Backend>(
+    f: &mut tui::Frame<B>,
+    rect: Rect,
+    width: u16,
+    left: &[(&'static str, KeyCode, AiSide)],
+    right: &[(&'static str, KeyCode, AiSide)],
+    targets: &mut Targets,
+) {
+    // Same offset as centered paragraphs
+    let start = rect.x + (rect.width / 2).saturating_sub(width / 2);
+    let row = rect.y + rect.height.saturating_sub(1);
+    let buttons = left
+        .iter()
+        .enumerate()
+        .map(|(i, b)| (start.checked_sub((i as u16 + 1) * (BUTTON_WIDTH + 1)), b))
+        .chain(
+            right
+                .iter()
+                .enumerate()
+                .map(|(i, b)| (Some(start + width + 1 + i as u16 * (BUTTON_WIDTH + 1)), b)),
+        );
+    for (x, (label, code, ai_side)) in buttons {
+        let button = match x {
+            Some(x) if x >= rect.left() && x + BUTTON_WIDTH <= rect.right() => {
+                Rect::new(x, row, BUTTON_WIDTH, 1)
+            }
+            _ => continue,
+        };
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                *label,
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .fg(ai_side.color()),
+            )),
+            button,
+        );
+        targets.add(button, *code);
+    }
 }
 
 impl Game {
-    pub fn draw_captures<B: Backend>(&self, f: &mut tui::Frame<B>, rect: Rect) {
+    pub fn draw_captures<B: Backend>(
+        &self,
+        f: &mut tui::Frame<B>,
+        rect: Rect,
+        targets: &mut Targets,
+    ) {
         let marged = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(100)])
@@ -29,10 +113,10 @@ impl Game {
             .split(rect)[0];
 
         let block = Block::default()
-            .title(" Captures ")
+            .title(format!(" {} ", tr!("capture.title")))
             .title_alignment(Alignment::Left)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow));
+            .border_style(Style::default().fg(theme().highlight));
         f.render_widget(block, marged);
 
         let capture_chunk = Layout::default()
@@ -48,30 +132,44 @@ impl Game {
             .margin(1)
             .split(marged);
 
-        let for_ai_block = Block::default()
-            .title("For AI Captures")
-            .title_alignment(Alignment::Center);
-        let center_capture_block = Block::default()
-            .title("Center")
-            .title_alignment(Alignment::Center);
-        let against_ai_block = Block::default()
-            .title("Against AI Captures")
-            .title_alignment(Alignment::Center);
+        for (point, chunk) in self.captures.iter().zip(capture_chunk.iter()) {
+            let mut title = point.location.name();
+            if let Some(position) = &point.position {
+                title = format!("{} {}", title, position);
+            }
+            // Frozen by Lucy's firewall, or both teams on it
+            if self.is_frozen(&point.location) {
+                title = format!("{} ({})", title, tr!("capture.frozen"));
+            } else if point.is_contested() {
+                title = format!("{} ({})", title, tr!("capture.contested"));
+            }
+            let block = Block::default()
+                .title(title)
+                .title_alignment(Alignment::Center);
+            let occupants = occupants_from(point);
+            let width = occupants.width() as u16;
+            f.render_widget(
+                Paragraph::new(occupants)
+                    .block(block)
+                    .alignment(Alignment::Center),
+                *chunk,
+            );
 
-        f.render_widget(
-            capture_from(&self.for_ai.as_ref().unwrap().capture).block(for_ai_block),
-            capture_chunk[0],
-        );
-        match &self.center_capture {
-            Some(capture) => f.render_widget(
-                capture_from(&capture).block(center_capture_block),
-                capture_chunk[1],
-            ),
-            None => f.render_widget(center_capture_block, capture_chunk[1]),
-        };
-        f.render_widget(
-            capture_from(&self.against_ai.as_ref().unwrap().capture).block(against_ai_block),
-            capture_chunk[2],
-        );
+            let ((for_remove, for_add), (against_remove, against_add)) = keys_of(&point.location);
+            draw_buttons(
+                f,
+                *chunk,
+                width,
+                &[
+                    ("[-]", KeyCode::Char(for_remove), AiSide::For),
+                    ("[+]", KeyCode::Char(for_add), AiSide::For),
+                ],
+                &[
+                    ("[-]", KeyCode::Char(against_remove), AiSide::Against),
+                    ("[+]", KeyCode::Char(against_add), AiSide::Against),
+                ],
+                targets,
+            );
+        }
     }
 }
@@ -1,70 +1,68 @@
-use rand::{seq::SliceRandom, Rng};
+use crossterm::event::KeyCode;
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
 };
 
 use crate::app::{
-    data::game::{Game, CHALLENGE_POINTS},
+    data::{
+        challenge::{Challenge, ChallengeOutcome, Vote},
+        game::AiSide,
+        race::SECS_IN_MIN,
+    },
+    i18n::tr,
+    theme::theme,
     App,
 };
 
-const SOLO_CHALLENGES: &'static [&'static str] = &[
-    "[DUEL] Shifumi",
-    "[DUEL] Tenir le plus longtemps sur un pied",
-    "[DUEL] Celui qui saute le plus haut",
-    "[DUEL] Celui qui fait le meilleur avion en papier (nécessite papier)",
-    "[DUEL] Premier joueur à trouver un objet [rose, à poil, ...]",
-    "[DUEL] Guerre des pouces",
-    "[DUEL] Citer l'un après l'autre des [marques de voitures, Pokémon, …]. Le premier a ne pas trouver en 5 secondes perds",
-    "[DUEL] Le premier à finir un verre d'eau (nécessite eau)",
-    "[DUEL] Le premier joueur à prononcer faux «panier piano» perd",
-    "[DUEL] Le joueur qui arrive à jongler le plus longtemps gagne (nécessite trucs pour jongler)",
-    "[DUEL] Pile ou face",
-    "[DUEL] Celui qui dessine le meilleur [chat, fruit, …] en 30s",
-    "[DUEL] Le premier à toucher le pied de l'autre joueur",
-    "[DUEL] Combat de regard",
-    "[DUEL] Le premier joueur a compter juste le nombre de cases posées sur le plateau gagne",
-    "[DUEL] Celui qui court le plus vite en sprint",
-    "[DUEL] Celui qui fait le plus de pompes",
-    "[DUEL] Celui qui imite le mieux une célébrité",
-];
-
-const TEAM_CHALLENGES: &'static [&'static str] = &[
-    "[TEAM] L'équipe qui enlève le plus vite ses chaussettes",
-    "[TEAM] L'équipe qui construit la plus haute tour en 1 minutes",
-    "[TEAM] La première équipe a citer 3 [animaux verts, sports d'hiver, acteurs, …]",
-    "[TEAM] La première équipe à tous lever un membre de son corps (bras, jambes)",
-    "[TEAM] La première équipe à tous toucher le plafond",
-    "[TEAM] La première équipe à entièrement sortir de la pièce",
-    "[TEAM] La première équipe à ne plus toucher le sol",
-    "[TEAM] La première équipe à faire un cercle en se tenant les bras",
-    "[TEAM] La première équipe à tous se toucher les genoux",
-    "[TEAM] La première équipe à réciter les deux premières lignes d'un poème",
-    "[TEAM] Chaque équipe choisit 3 lettres différentes de l'alphabet, l'équipe qui trouve le plus de mots différents en 1 minute gagne",
-];
-
-impl Game {
-    pub fn pick_challenge(&self) -> &'static str {
-        let mut rng = rand::thread_rng();
-
-        if let Some(for_ai) = self.for_ai.as_ref() {
-            if let Some(against_ai) = self.against_ai.as_ref() {
-                if for_ai.nb_players > 1 && against_ai.nb_players > 1 {
-                    return match rng.gen_range(0..=1) {
-                        0 => SOLO_CHALLENGES.choose(&mut rng).unwrap(),
-                        1 => TEAM_CHALLENGES.choose(&mut rng).unwrap(),
-                        _ => panic!("Should never happen!"),
-                    };
-                }
-            }
-        }
+type Hint = (Vec<Span<'static>>, Option<KeyCode>);
 
-        return SOLO_CHALLENGES.choose(&mut rng).unwrap();
-    }
+fn hint(key: &'static str, text: String, code: KeyCode) -> Hint {
+    let key_style = Style::default()
+        .add_modifier(Modifier::BOLD)
+        .fg(theme().key);
+    (
+        vec![
+            Span::styled(key, key_style),
+            Span::from(format!(": {}", text)),
+        ],
+        Some(code),
+    )
+}
+
+fn gap() -> Hint {
+    (vec![Span::from("    ")], None)
+}
+
+fn timer_from(challenge: &Challenge) -> Hint {
+    let remaining = match challenge.remaining_time() {
+        Some(remaining) => remaining,
+        None => return (vec![Span::from(tr!("challenge.no_timer"))], None),
+    };
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let time = if challenge.is_over() {
+        Span::styled(
+            format!(" {} ", tr!("challenge.time_up")),
+            bold.bg(theme().error).fg(theme().background),
+        )
+    } else {
+        Span::styled(
+            format!(
+                " {}:{:02} ",
+                remaining.num_minutes(),
+                remaining.num_seconds() as u64 % SECS_IN_MIN
+            ),
+            if challenge.is_running() {
+                bold.bg(theme().accent).fg(theme().background)
+            } else {
+                bold.fg(theme().accent)
+            },
+        )
+    };
+    (vec![time], None)
 }
 
 impl App<'_> {
@@ -72,16 +70,15 @@ impl App<'_> {
         &mut self,
         f: &mut tui::Frame<B>,
         rect: Rect,
-        text: &'static str,
+        challenge: &Challenge,
     ) {
         let marged = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(100)])
             .horizontal_margin(50)
-            .vertical_margin(20)
+            .vertical_margin(10)
             .split(rect)[0];
 
-        // TODO key desc
         let col = Layout::default()
             .direction(Direction::Vertical)
             .constraints(
@@ -89,8 +86,9 @@ impl App<'_> {
                     Constraint::Length(3), // Introduction msg
                     Constraint::Length(1),
                     Constraint::Length(6), // Challenge description
+                    Constraint::Length(3), // Timer
                     Constraint::Length(1),
-                    Constraint::Length(3), // Keys
+                    Constraint::Length(4), // Keys
                     Constraint::Min(0),
                 ]
                 .as_ref(),
@@ -99,10 +97,10 @@ impl App<'_> {
             .split(marged);
 
         let outer_block = Block::default()
-            .title(" Challenge ! ")
+            .title(format!(" {} ", tr!("challenge.title")))
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Magenta));
+            .border_style(Style::default().fg(theme().accent));
 
         f.render_widget(outer_block, marged);
 
@@ -113,20 +111,26 @@ impl App<'_> {
             .split(col[2])[0];
 
         let prompt_block = Block::default()
-            .title(" Description ")
+            .title(format!(" {} ", tr!("challenge.description")))
             .title_alignment(Alignment::Left)
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow));
+            .border_style(Style::default().fg(theme().highlight));
 
         f.render_widget(prompt_block, desc_row);
 
         let msg = Paragraph::new(vec![
             Spans::from(""),
-            Spans::from("You have to do the following challenge (either as a team or in a duel)."),
-            Spans::from(format!(
-                "The winning team receives {} points !",
-                CHALLENGE_POINTS
-            )),
+            Spans::from(tr!("challenge.intro")),
+            if self.game.sudden_death {
+                Spans::from(Span::styled(
+                    tr!("challenge.sudden_death"),
+                    Style::default()
+                        .fg(theme().accent)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else {
+                Spans::from(tr!("challenge.reward", challenge.points))
+            },
         ])
         .alignment(Alignment::Center);
         f.render_widget(msg, col[0]);
@@ -138,35 +142,294 @@ impl App<'_> {
             .horizontal_margin(2)
             .split(desc_row)[0];
 
-        let prompt = Paragraph::new(vec![Spans::from(""), Spans::from(Spans::from(text))])
+        let prompt = Paragraph::new(vec![Spans::from(""), Spans::from(challenge.text.as_str())])
             .wrap(Wrap { trim: false })
             .alignment(Alignment::Center)
             .style(Style::default().add_modifier(Modifier::BOLD));
 
         f.render_widget(prompt, prompt_row);
 
-        let key_style = Style::default()
-            .add_modifier(Modifier::BOLD)
-            .fg(Color::Rgb(138, 138, 138));
-
-        let key = Paragraph::new(vec![
-            Spans::from(vec![
-                Span::styled("[Q]", key_style),
-                Span::from(": Cancel Challenge"),
-                Span::from("    "),
-                Span::styled("[F]", key_style),
-                Span::from(": Change Challenge"),
-            ]),
-            Spans::from(vec![
-                Span::styled("  [1]", key_style),
-                Span::from(": Team For AI won"),
-                Span::from("     "),
-                Span::styled("[0]", key_style),
-                Span::from(": Team Against AI won"),
-            ]),
-        ])
-        .alignment(Alignment::Center);
+        // Countdown, its duration can be set before starting it
+        let timer_row = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(100)].as_ref())
+            .horizontal_margin(5)
+            .split(col[3])[0];
+        let timer_block = Block::default()
+            .title(format!(" {} ", tr!("challenge.timer")))
+            .title_alignment(Alignment::Left)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().accent));
+        let timer_inner = timer_block.inner(timer_row);
+        f.render_widget(timer_block, timer_row);
+
+        let mut timer = vec![timer_from(challenge), gap()];
+        if challenge.is_running() {
+            timer.push(hint(
+                "[T]",
+                tr!("challenge.stop_timer").to_string(),
+                KeyCode::Char('t'),
+            ));
+        } else {
+            if challenge.duration.is_some() {
+                timer.push(hint(
+                    "[T]",
+                    tr!("challenge.start_timer").to_string(),
+                    KeyCode::Char('t'),
+                ));
+                timer.push(gap());
+            }
+            timer.push(hint("[←]", "-10s".to_string(), KeyCode::Left));
+            timer.push(gap());
+            timer.push(hint("[→]", "+10s".to_string(), KeyCode::Right));
+        }
+        f.render_widget(
+            Paragraph::new(self.targets.hint_line(timer_inner, timer)).alignment(Alignment::Center),
+            timer_inner,
+        );
+
+        let mut options = vec![];
+        if !self.game.sudden_death {
+            options.push(hint(
+                "[Q]",
+                tr!("challenge.cancel").to_string(),
+                KeyCode::Char('q'),
+            ));
+            options.push(gap());
+        }
+        options.push(hint(
+            "[F]",
+            tr!("challenge.change").to_string(),
+            KeyCode::Char('f'),
+        ));
+        options.push(gap());
+        options.push(hint(
+            "[B]",
+            tr!("challenge.ban").to_string(),
+            KeyCode::Char('b'),
+        ));
+        if challenge.is_template() {
+            options.push(gap());
+            options.push(hint(
+                "[Shift+F]",
+                tr!("challenge.reroll").to_string(),
+                KeyCode::Char('F'),
+            ));
+        }
+
+        let mut winners = vec![
+            hint(
+                "[1]",
+                tr!("challenge.won", AiSide::For.name()),
+                KeyCode::Char('1'),
+            ),
+            gap(),
+            hint(
+                "[0]",
+                tr!("challenge.won", AiSide::Against.name()),
+                KeyCode::Char('0'),
+            ),
+        ];
+
+        let judges = self.game.judges();
+        if challenge.judged && judges > 0 {
+            winners.push(gap());
+            winners.push(hint(
+                "[J]",
+                tr!("challenge.judge", judges),
+                KeyCode::Char('j'),
+            ));
+        }
 
-        f.render_widget(key, col[4]);
+        let mut others = vec![
+            hint(
+                "[D]",
+                if self.game.sudden_death {
+                    tr!("challenge.draw_replay").to_string()
+                } else {
+                    tr!("challenge.draw", challenge.points / 2)
+                },
+                KeyCode::Char('d'),
+            ),
+            gap(),
+            hint(
+                "[X]",
+                tr!("challenge.both_failed").to_string(),
+                KeyCode::Char('x'),
+            ),
+        ];
+        if !self.game.sudden_death {
+            others.push(gap());
+            others.push(hint(
+                "[↑]",
+                tr!("challenge.more_points").to_string(),
+                KeyCode::Up,
+            ));
+            others.push(gap());
+            others.push(hint(
+                "[↓]",
+                tr!("challenge.less_points").to_string(),
+                KeyCode::Down,
+            ));
+        }
+
+        let row = |i: u16| Rect {
+            y: col[5].y + i,
+            height: 1,
+            ..col[5]
+        };
+        let key = match &challenge.vote {
+            Some(vote) if !vote.is_complete() => self.draw_ballot(vote, row),
+            vote => {
+                let mut lines = vec![
+                    self.targets.hint_line(row(0), options),
+                    self.targets.hint_line(row(1), winners),
+                    self.targets.hint_line(row(2), others),
+                ];
+                // Only a tie leaves the vote to the referee
+                if let Some(vote) = vote {
+                    lines.push(Spans::from(Span::styled(
+                        tr!("challenge.tied_vote", vote.for_ai, vote.against_ai),
+                        Style::default()
+                            .fg(theme().accent)
+                            .add_modifier(Modifier::BOLD),
+                    )));
+                }
+                lines
+            }
+        };
+        let key = Paragraph::new(key).alignment(Alignment::Center);
+
+        f.render_widget(key, col[5]);
+    }
+}
+
+impl App<'_> {
+    // Ballots stay hidden until the last judge voted
+    fn draw_ballot(&mut self, vote: &Vote, row: impl Fn(u16) -> Rect) -> Vec<Spans<'static>> {
+        let ballot = Span::styled(
+            tr!("challenge.ballot", vote.cast_count() + 1, vote.voters),
+            Style::default()
+                .fg(theme().accent)
+                .add_modifier(Modifier::BOLD),
+        );
+        let choices = vec![
+            hint("[1]", AiSide::For.name().to_string(), KeyCode::Char('1')),
+            gap(),
+            hint(
+                "[0]",
+                AiSide::Against.name().to_string(),
+                KeyCode::Char('0'),
+            ),
+            gap(),
+            hint(
+                "[A]",
+                tr!("challenge.abstain").to_string(),
+                KeyCode::Char('a'),
+            ),
+        ];
+        let cancel = vec![hint(
+            "[Q]",
+            tr!("challenge.cancel_vote").to_string(),
+            KeyCode::Char('q'),
+        )];
+        vec![
+            Spans::from(ballot),
+            self.targets.hint_line(row(1), choices),
+            self.targets.hint_line(row(2), cancel),
+        ]
+    }
+}
+
+impl App<'_> {
+    pub fn draw_challenge_history<B: Backend>(&mut self, f: &mut tui::Frame<B>, rect: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .vertical_margin(5)
+            .horizontal_margin(30)
+            .constraints([Constraint::Min(5), Constraint::Length(3)].as_ref())
+            .split(rect);
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let key_style = bold.fg(theme().key);
+
+        let history = &self.deck.history;
+        // Keep the last rows on screen when scrolling
+        let visible = chunks[0].height.saturating_sub(4) as usize;
+        self.scroll = self
+            .scroll
+            .min(history.len().saturating_sub(visible) as u16);
+
+        let rows: Vec<Row> = history
+            .iter()
+            .enumerate()
+            .skip(self.scroll as usize)
+            .map(|(i, played)| {
+                let result = match &played.outcome {
+                    Some(ChallengeOutcome::Won(ai_side)) => {
+                        let name = match (&played.team_names, ai_side) {
+                            (Some((home, _)), AiSide::For) => home.as_str(),
+                            (Some((_, away)), AiSide::Against) => away.as_str(),
+                            (None, _) => ai_side.name(),
+                        };
+                        Span::styled(
+                            format!("{}{}", ai_side.mark(), name),
+                            bold.fg(ai_side.color()),
+                        )
+                    }
+                    Some(ChallengeOutcome::Draw) => Span::from(tr!("played.draw")),
+                    Some(ChallengeOutcome::BothFailed) => Span::from(tr!("played.both_failed")),
+                    None => Span::styled(tr!("played.cancelled"), Style::default().fg(theme().key)),
+                };
+                Row::new(vec![
+                    Cell::from(format!("{}.", i + 1)),
+                    Cell::from(played.text.clone()),
+                    Cell::from(result),
+                ])
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(5),
+            // Borders and column spacing
+            Constraint::Length(chunks[0].width.saturating_sub(5 + 20 + 4)),
+            Constraint::Length(20),
+        ];
+        let table = Table::new(rows)
+            .header(
+                Row::new(vec!["#", tr!("played.challenge"), tr!("played.winner")])
+                    .style(bold.fg(theme().highlight))
+                    .bottom_margin(1),
+            )
+            .block(
+                Block::default()
+                    .title(format!(" {} ", tr!("played.title")))
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().accent)),
+            )
+            .widths(&widths);
+        f.render_widget(table, chunks[0]);
+
+        let mut lines = vec![Spans::from(vec![
+            Span::styled("[↑/↓]", key_style),
+            Span::from(format!(": {}    ", tr!("hint.scroll"))),
+            Span::styled("[Enter]", key_style),
+            Span::from(format!(": {}", tr!("hint.back"))),
+        ])];
+        if history.is_empty() {
+            lines.insert(0, Spans::from(tr!("played.empty")));
+        }
+        if !self.deck.banned.is_empty() {
+            let banned: Vec<&str> = self.deck.banned.iter().map(|key| tr!(key)).collect();
+            lines.push(Spans::from(Span::styled(
+                tr!("played.banned", banned.join(", ")),
+                Style::default().fg(theme().error),
+            )));
+        }
+        f.render_widget(
+            Paragraph::new(lines).alignment(Alignment::Center),
+            chunks[1],
+        );
     }
 }
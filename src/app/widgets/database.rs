@@ -0,0 +1,160 @@
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::app::{
+    data::{
+        database::{DatabaseError, Raid},
+        game::{AiSide, PositionError},
+    },
+    i18n::tr,
+    theme::theme,
+    App,
+};
+
+impl Raid {
+    pub fn name(&self) -> &str {
+        match self {
+            Raid::Off => tr!("raid.off"),
+            Raid::Steal => tr!("raid.steal"),
+            Raid::Destroy => tr!("raid.destroy"),
+        }
+    }
+}
+
+impl PositionError {
+    pub fn description(&self) -> &str {
+        match self {
+            PositionError::Format => tr!("position.format"),
+            PositionError::OffBoard => tr!("position.off_board"),
+        }
+    }
+}
+
+impl DatabaseError {
+    pub fn description(&self) -> &str {
+        match self {
+            DatabaseError::Position(err) => err.description(),
+            DatabaseError::NoSupply => tr!("database.no_supply"),
+            DatabaseError::Taken => tr!("database.taken"),
+        }
+    }
+}
+
+impl App<'_> {
+    // Replaces the keys panel while the referee enters a database position
+    pub fn draw_database_input<B: Backend>(
+        &mut self,
+        f: &mut tui::Frame<B>,
+        rect: Rect,
+        ai_side: AiSide,
+    ) {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let key_style = bold.fg(theme().key);
+
+        let block = Block::default()
+            .title(format!(" {} ", tr!("database.title", ai_side.label())))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(ai_side.color()));
+        let inner = block.inner(rect);
+        f.render_widget(block, rect);
+
+        // Placed databases of each team, "?" when the position was not entered
+        let placed = |owner: AiSide| {
+            let positions: Vec<String> = self
+                .game
+                .databases
+                .iter()
+                .filter(|database| !database.destroyed && database.owner == owner)
+                .map(|database| {
+                    database
+                        .position
+                        .map_or("?".to_string(), |position| position.to_string())
+                })
+                .collect();
+            Span::styled(
+                format!(
+                    "{} {}",
+                    owner.label(),
+                    if positions.is_empty() {
+                        "-".to_string()
+                    } else {
+                        positions.join(" ")
+                    }
+                ),
+                Style::default().fg(owner.color()),
+            )
+        };
+
+        let mut lines = vec![
+            Spans::from(Span::from(tr!(
+                "database.prompt",
+                ai_side.label(),
+                self.game.supply_left(&ai_side)
+            ))),
+            Spans::from(""),
+            Spans::from(vec![
+                Span::from(format!("{} ", tr!("database.position"))),
+                Span::styled(format!("{}_", self.input), bold),
+            ]),
+            Spans::from(match &self.database_error {
+                Some(err) => Span::styled(err.description(), Style::default().fg(theme().error)),
+                None => Span::from(""),
+            }),
+            Spans::from(vec![
+                Span::from(format!("{} ", tr!("database.placed"))),
+                placed(AiSide::For),
+                Span::from("    "),
+                placed(AiSide::Against),
+            ]),
+        ];
+        if self.game.rules.raid != Raid::Off {
+            lines.push(Spans::from(Span::styled(
+                tr!("database.raid", self.game.rules.raid.name()),
+                Style::default().fg(theme().text),
+            )));
+        } else {
+            lines.push(Spans::from(""));
+        }
+
+        let hints_row = Rect {
+            y: inner.y + lines.len() as u16,
+            height: 1,
+            ..inner
+        };
+        lines.push(self.targets.hint_line(
+            hints_row,
+            vec![
+                (
+                    vec![
+                        Span::styled("[←↑↓→]", key_style),
+                        Span::from(format!(": {}  ", tr!("hint.pick_position"))),
+                    ],
+                    None,
+                ),
+                (
+                    vec![
+                        Span::styled("[Enter]", key_style),
+                        Span::from(format!(": {}  ", tr!("hint.place"))),
+                    ],
+                    Some(KeyCode::Enter),
+                ),
+                (
+                    vec![
+                        Span::styled("[Backspace]", key_style),
+                        Span::from(format!(": {}", tr!("hint.erase"))),
+                    ],
+                    Some(KeyCode::Backspace),
+                ),
+            ],
+        ));
+
+        f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), inner);
+    }
+}
@@ -0,0 +1,64 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::Span,
+    widgets::Paragraph,
+};
+
+use crate::app::{
+    data::{
+        capture::Location,
+        event::EventKind,
+        game::{Game, DATABASE_POINTS},
+    },
+    i18n::tr,
+    theme::theme,
+};
+
+impl EventKind {
+    pub fn description(&self) -> String {
+        match self {
+            EventKind::Firewall(Location::Home(ai_side)) => {
+                tr!("event.firewall", ai_side.label())
+            }
+            EventKind::Firewall(Location::Center) => tr!("event.firewall", tr!("capture.center")),
+            EventKind::Blackout => tr!("event.blackout").to_string(),
+            EventKind::Overclock => tr!("event.overclock").to_string(),
+            EventKind::Teleport(ai_side, Some(vpn)) => {
+                tr!("event.teleport_to", ai_side.label(), vpn)
+            }
+            EventKind::Teleport(ai_side, None) => tr!("event.teleport", ai_side.label()),
+            EventKind::VpnMove(ai_side, vpn) => tr!("event.vpn_move", ai_side.label(), vpn),
+            EventKind::DataLeak(ai_side) => {
+                tr!("event.data_leak", ai_side.label(), DATABASE_POINTS)
+            }
+        }
+    }
+}
+
+impl Game {
+    // Banner of the running Lucy event
+    pub fn draw_event<B: Backend>(&self, f: &mut tui::Frame<B>, rect: Rect) {
+        let event = match &self.event {
+            Some(event) => event,
+            None => return,
+        };
+        let banner = tr!(
+            "event.banner",
+            event.kind.description(),
+            event.rounds_left()
+        );
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                format!(" {} ", banner),
+                Style::default()
+                    .add_modifier(Modifier::BOLD)
+                    .bg(theme().accent)
+                    .fg(theme().background),
+            ))
+            .alignment(Alignment::Center),
+            rect,
+        );
+    }
+}
@@ -1,19 +1,25 @@
+use crossterm::event::KeyCode;
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::app::{data::game::AiSide, App};
+use crate::app::{
+    data::game::AiSide,
+    i18n::{self, tr},
+    theme::theme,
+    App,
+};
 
 impl App<'_> {
     pub fn draw_finished<B: Backend>(
         &mut self,
         f: &mut tui::Frame<B>,
         rect: Rect,
-        ai_side: AiSide,
+        ai_side: Option<AiSide>,
     ) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -22,17 +28,19 @@ impl App<'_> {
                     Constraint::Length(10), // Art
                     Constraint::Length(1),
                     Constraint::Length(7), // Success msg
-                    Constraint::Length(2),
+                    Constraint::Length(1),
+                    Constraint::Length(20), // Report and progress over time
+                    Constraint::Length(1),
                     Constraint::Length(4 + 5), // Credits
                     Constraint::Min(1),
                 ]
                 .as_ref(),
             )
             .horizontal_margin(50)
-            .vertical_margin(10)
+            .vertical_margin(2)
             .split(rect);
 
-        let art_style = Style::default().fg(Color::Magenta);
+        let art_style = Style::default().fg(theme().accent);
 
         let art_msg = Paragraph::new(vec![
             Spans::from(Span::styled(
@@ -72,44 +80,107 @@ impl App<'_> {
         f.render_widget(art_msg.alignment(Alignment::Center), chunks[0]);
 
         let success_block = Block::default()
-            .title(" The END ")
+            .title(format!(" {} ", tr!("finished.end")))
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL);
 
-        let success_msg = Paragraph::new(vec![
+        let mut success_msg = vec![
             Spans::from(""),
+            match &ai_side {
+                Some(ai_side) => {
+                    let (before, after) = i18n::split(tr!("finished.won"));
+                    Spans::from(vec![
+                        Span::from(before),
+                        Span::styled(
+                            format!(
+                                "{}{}",
+                                ai_side.mark(),
+                                self.game.team_name(ai_side).unwrap_or(ai_side.name())
+                            ),
+                            Style::default()
+                                .fg(ai_side.color())
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::from(after),
+                    ])
+                }
+                None => Spans::from(Span::styled(
+                    tr!("finished.draw"),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+            },
             Spans::from(vec![
-                Span::from("Well done! The "),
-                Span::styled(
-                    ai_side.name(),
-                    Style::default()
-                        .fg(ai_side.color())
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::from(" team won the game!"),
+                Span::from(tr!("finished.thanks")),
+                Span::styled(" :)", Style::default().fg(theme().success)),
             ]),
-            Spans::from(vec![
-                Span::from("Thanks for playing, we hope you enjoyed"),
-                Span::styled(" :)", Style::default().fg(Color::Green)),
-            ]),
-            Spans::from("Press [ESC] to quit."),
-        ])
-        .alignment(Alignment::Center);
+        ];
+        if self.tournament_match.is_some() {
+            success_msg.push(Spans::from(tr!("finished.record")));
+        } else {
+            // Row below the three lines above, inside the border
+            let hints_row = Rect {
+                y: chunks[2].y + 4,
+                height: 1,
+                ..success_block.inner(chunks[2])
+            };
+            let key_style = Style::default()
+                .fg(theme().key)
+                .add_modifier(Modifier::BOLD);
+            success_msg.push(self.targets.hint_line(
+                hints_row,
+                vec![
+                    (
+                        vec![
+                            Span::styled("[a]", key_style),
+                            Span::from(format!(": {}    ", tr!("hint.play_again"))),
+                        ],
+                        Some(KeyCode::Char('a')),
+                    ),
+                    (
+                        vec![
+                            Span::styled("[ESC]", key_style),
+                            Span::from(format!(": {}", tr!("hint.quit"))),
+                        ],
+                        Some(KeyCode::Esc),
+                    ),
+                ],
+            ));
+        }
+        // The game could not be archived
+        if let Some(err) = &self.history_error {
+            success_msg.push(Spans::from(Span::styled(
+                err.clone(),
+                Style::default().fg(theme().error),
+            )));
+        }
+
+        f.render_widget(
+            Paragraph::new(success_msg)
+                .alignment(Alignment::Center)
+                .block(success_block),
+            chunks[2],
+        );
 
-        f.render_widget(success_msg.block(success_block), chunks[2]);
+        // What the points came from next to where the game swung
+        let report_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(3, 5), Constraint::Ratio(2, 5)].as_ref())
+            .split(chunks[4]);
+        self.game.draw_report(f, report_chunks[0], ai_side.as_ref());
+        self.game.draw_timeline(f, report_chunks[1]);
 
         let credits_chucks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Length(23)].as_ref())
             .horizontal_margin(20)
-            .split(chunks[4]);
+            .split(chunks[6]);
 
         let credits_block = Block::default()
-            .title("Credits")
+            .title(tr!("finished.credits"))
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL);
 
-        let credits_style = Style::default().fg(Color::Yellow);
+        let credits_style = Style::default().fg(theme().highlight);
 
         let credits_msg = Paragraph::new(vec![
             Spans::from(""),
@@ -0,0 +1,359 @@
+use std::time::Duration;
+
+use itertools::Itertools;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+};
+
+use crate::app::{
+    data::{
+        game::{AiSide, Outcome},
+        history::{Filter, Record, Stats, Team},
+        score::SOURCES,
+        timeline::{challenge_record, largest_lead, lead_changes},
+    },
+    i18n::tr,
+    theme::theme,
+    App,
+};
+
+pub fn duration_text(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+impl Team {
+    // Tournament name, else the players, else how many they were
+    pub fn label(&self) -> String {
+        let players: Vec<&String> = self.players.iter().filter(|p| !p.is_empty()).collect();
+        match &self.name {
+            Some(name) => name.clone(),
+            None if !players.is_empty() => players.iter().join(", "),
+            None => tr!("history.players", self.nb_players),
+        }
+    }
+}
+
+impl Filter {
+    pub fn outcome_name(&self) -> String {
+        match &self.outcome {
+            None => tr!("history.all").to_string(),
+            Some(Outcome::Won(ai_side)) => tr!("history.won", ai_side.name()),
+            Some(Outcome::Level) => tr!("history.draw").to_string(),
+        }
+    }
+}
+
+impl Record {
+    pub fn result(&self) -> String {
+        match &self.winner {
+            Some(ai_side) => tr!("history.won", ai_side.name()),
+            None => tr!("history.draw").to_string(),
+        }
+    }
+
+    // One line of a list of games
+    pub fn summary(&self) -> String {
+        format!(
+            "#{:<4} {}  {}  {} - {}  {}  {}  {}  {}",
+            self.id,
+            self.played,
+            self.for_ai.label(),
+            self.for_ai.progress,
+            self.against_ai.progress,
+            self.against_ai.label(),
+            self.result(),
+            self.mode.name(),
+            duration_text(self.duration)
+        )
+    }
+
+    // Everything kept about the game, one line per topic
+    pub fn details(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "#{}  {}  {} {}  {}",
+            self.id,
+            self.played,
+            tr!("report.duration"),
+            duration_text(self.duration),
+            self.mode.name()
+        )];
+        for ai_side in [AiSide::For, AiSide::Against] {
+            let team = self.team(&ai_side);
+            lines.push(format!(
+                "{}  {}  {} {}  {} {}",
+                ai_side.label(),
+                team.label(),
+                tr!("history.progress"),
+                team.progress,
+                tr!("side.nb_rounds"),
+                team.nb_rounds
+            ));
+        }
+        lines.push(format!("{} {}", tr!("history.result_label"), self.result()));
+        lines.push(format!(
+            "{} {}  {} {}",
+            tr!("setup.code"),
+            self.code.as_deref().unwrap_or("-"),
+            tr!("history.seed"),
+            self.seed.map_or("-".to_string(), |seed| seed.to_string())
+        ));
+        let points = SOURCES
+            .iter()
+            .map(|source| {
+                (
+                    source,
+                    self.points_from(&AiSide::For, *source),
+                    self.points_from(&AiSide::Against, *source),
+                )
+            })
+            .filter(|(_, for_ai, against_ai)| *for_ai != 0 || *against_ai != 0)
+            .map(|(source, for_ai, against_ai)| {
+                format!("{} {:+}/{:+}", source.name(), for_ai, against_ai)
+            })
+            .join("  ");
+        lines.push(format!("{} {}", tr!("history.points"), points));
+        let lead = match largest_lead(&self.timeline) {
+            Some((ai_side, lead, turn)) => format!(
+                "{} {} {}",
+                tr!("report.largest_lead"),
+                ai_side.name(),
+                tr!("report.lead", lead, turn)
+            ),
+            None => tr!("report.no_lead").to_string(),
+        };
+        let changes = lead_changes(&self.timeline);
+        let changes = if changes.is_empty() {
+            tr!("report.no_lead_change").to_string()
+        } else {
+            tr!(
+                "report.lead_changes",
+                changes.iter().map(|(turn, _)| turn).join(", ")
+            )
+        };
+        lines.push(format!("{}  {}", lead, changes));
+        let (for_ai, against_ai, undecided) = challenge_record(&self.timeline);
+        lines.push(format!(
+            "{} {} {}  {} {}  {}",
+            tr!("report.challenge_record"),
+            AiSide::For.name(),
+            for_ai,
+            AiSide::Against.name(),
+            against_ai,
+            tr!("report.undecided", undecided)
+        ));
+        lines.push(self.rules.join("  ·  "));
+        lines
+    }
+}
+
+impl Stats {
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "{} {}  {} {}  {} {}  {} {}",
+                tr!("history.games"),
+                self.games,
+                tr!("history.wins", AiSide::For.name()),
+                self.for_wins,
+                tr!("history.wins", AiSide::Against.name()),
+                self.against_wins,
+                tr!("history.draws"),
+                self.draws
+            ),
+            format!(
+                "{} {}  {} {:.1}",
+                tr!("history.average_duration"),
+                duration_text(self.average_duration),
+                tr!("history.average_turns"),
+                self.average_turns
+            ),
+        ];
+        if let Some((deficit, id)) = self.biggest_comeback {
+            lines.push(tr!("history.comeback", deficit, id));
+        }
+        lines.push(format!(
+            "{} {}",
+            tr!("history.points"),
+            self.points
+                .iter()
+                .filter(|(_, points)| *points != 0)
+                .map(|(source, points)| format!("{} {:+}", source.name(), points))
+                .join("  ")
+        ));
+        if !self.top_players.is_empty() {
+            lines.push(format!(
+                "{} {}",
+                tr!("history.top_players"),
+                self.top_players
+                    .iter()
+                    .map(|(name, wins, games)| format!("{} {}/{}", name, wins, games))
+                    .join("  ")
+            ));
+        }
+        lines
+    }
+}
+
+impl App<'_> {
+    pub fn draw_history<B: Backend>(&mut self, f: &mut tui::Frame<B>, rect: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .vertical_margin(3)
+            .horizontal_margin(30)
+            .constraints(
+                [
+                    Constraint::Length(3),  // Filter
+                    Constraint::Min(6),     // Games
+                    Constraint::Length(13), // Details or stats
+                    Constraint::Length(3),  // Keys
+                ]
+                .as_ref(),
+            )
+            .split(rect);
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let key_style = bold.fg(theme().key);
+        let text = Style::default().fg(theme().text);
+        let block = |title: &str| {
+            Block::default()
+                .title(format!(" {} ", title))
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+        };
+
+        let name = if self.entering_filter {
+            Span::styled(format!("{}_", self.input), bold.fg(theme().highlight))
+        } else if self.history_filter.name.is_empty() {
+            Span::styled(tr!("history.any"), text)
+        } else {
+            Span::styled(self.history_filter.name.clone(), bold)
+        };
+        f.render_widget(
+            Paragraph::new(Spans::from(vec![
+                Span::from(format!("{} ", tr!("history.name"))),
+                name,
+                Span::styled("  [/]", key_style),
+                Span::from(format!("      {} ", tr!("history.result_label"))),
+                Span::styled(self.history_filter.outcome_name(), bold),
+                Span::styled("  [Tab]", key_style),
+            ]))
+            .alignment(Alignment::Center)
+            .block(block(tr!("history.filter"))),
+            chunks[0],
+        );
+
+        let games = self.history.filtered(&self.history_filter);
+        self.scroll = self.scroll.min(games.len().saturating_sub(1) as u16);
+        // Keep the selected game on screen
+        let visible = chunks[1].height.saturating_sub(4).max(1) as usize;
+        let offset = (self.scroll as usize + 1).saturating_sub(visible);
+
+        let rows: Vec<Row> = games
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .map(|(index, record)| {
+                let row = Row::new(vec![
+                    Cell::from(format!("#{}", record.id)),
+                    Cell::from(record.played.clone()),
+                    Cell::from(Span::styled(
+                        record.for_ai.label(),
+                        Style::default().fg(AiSide::For.color()),
+                    )),
+                    Cell::from(Span::styled(
+                        record.against_ai.label(),
+                        Style::default().fg(AiSide::Against.color()),
+                    )),
+                    Cell::from(format!(
+                        "{} - {}",
+                        record.for_ai.progress, record.against_ai.progress
+                    )),
+                    Cell::from(record.result()),
+                    Cell::from(record.mode.name()),
+                    Cell::from(duration_text(record.duration)),
+                ]);
+                if index == self.scroll as usize {
+                    row.style(bold.add_modifier(Modifier::REVERSED))
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        let table = Table::new(rows)
+            .header(
+                Row::new(vec![
+                    "#",
+                    tr!("history.played"),
+                    AiSide::For.name(),
+                    AiSide::Against.name(),
+                    tr!("history.score"),
+                    tr!("history.result"),
+                    tr!("history.mode"),
+                    tr!("history.duration"),
+                ])
+                .style(bold.fg(theme().highlight))
+                .bottom_margin(1),
+            )
+            .block(block(tr!("history.title")))
+            .widths(&[
+                Constraint::Length(5),
+                Constraint::Length(17),
+                Constraint::Percentage(15),
+                Constraint::Percentage(15),
+                Constraint::Length(9),
+                Constraint::Length(20),
+                Constraint::Length(22),
+                Constraint::Length(8),
+            ]);
+        f.render_widget(table, chunks[1]);
+
+        let selected = games.get(self.scroll as usize);
+        let (title, lines) = match selected {
+            Some(record) if self.history_details => (tr!("history.details"), record.details()),
+            _ => (tr!("history.stats"), Stats::new(&games).lines()),
+        };
+        f.render_widget(
+            Paragraph::new(lines.into_iter().map(Spans::from).collect::<Vec<_>>())
+                .style(text)
+                .wrap(Wrap { trim: true })
+                .block(block(title)),
+            chunks[2],
+        );
+
+        let mut lines = vec![Spans::from(vec![
+            Span::styled("[↑/↓]", key_style),
+            Span::from(format!(": {}    ", tr!("hint.select"))),
+            Span::styled("[Enter]", key_style),
+            Span::from(format!(": {}    ", tr!("hint.details"))),
+            Span::styled("[Del]", key_style),
+            Span::from(format!(": {}    ", tr!("hint.delete"))),
+            Span::styled("[Backspace]", key_style),
+            Span::from(format!(": {}", tr!("hint.back"))),
+        ])];
+        if games.is_empty() {
+            lines.insert(0, Spans::from(tr!("history.empty")));
+        }
+        match (selected, &self.history_error) {
+            (_, Some(err)) => lines.push(Spans::from(Span::styled(
+                err.clone(),
+                Style::default().fg(theme().error),
+            ))),
+            (Some(record), None) if self.confirm_delete => lines.push(Spans::from(Span::styled(
+                tr!("history.confirm_delete", record.id),
+                Style::default().fg(theme().error),
+            ))),
+            _ => {}
+        }
+        f.render_widget(
+            Paragraph::new(lines).alignment(Alignment::Center),
+            chunks[3],
+        );
+    }
+}
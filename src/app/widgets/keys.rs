@@ -1,17 +1,26 @@
+use crossterm::event::KeyCode::{Char, Tab, F};
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::app::data::game::Game;
+use itertools::Itertools;
+
+use super::{race::RACE_KEYS, vpn::VPN_KEYS};
+use crate::app::{
+    data::game::{AiSide, Game},
+    i18n::tr,
+    mouse::Targets,
+    theme::theme,
+};
 
 impl Game {
-    pub fn draw_keys<B: Backend>(&self, f: &mut tui::Frame<B>, rect: Rect) {
+    pub fn draw_keys<B: Backend>(&self, f: &mut tui::Frame<B>, rect: Rect, targets: &mut Targets) {
         let keys_block = Block::default()
-            .title(" Keys ")
+            .title(format!(" {} ", tr!("keys.title")))
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL);
 
@@ -32,84 +41,125 @@ impl Game {
 
         let key_style = Style::default()
             .add_modifier(Modifier::BOLD)
-            .fg(Color::Rgb(138, 138, 138));
+            .fg(theme().key);
 
+        // One claim key per race that can run at once
+        let race_keys = |key: fn(&(char, char)) -> char| {
+            format!(
+                "[{}]",
+                RACE_KEYS
+                    .iter()
+                    .take(self.rules.max_races)
+                    .map(key)
+                    .join("/")
+            )
+        };
         let col1 = vec![
             Spans::from(vec![
                 Span::styled("[ESC]", key_style),
-                Span::styled(": Exit game", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!(": {}", tr!("keys.exit")),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
             ]),
             Spans::from(vec![
                 Span::styled("[TAB]", key_style),
-                Span::from(": End turn"),
+                Span::from(format!(": {}", tr!("keys.end_turn"))),
+            ]),
+            Spans::from(vec![
+                Span::styled("[F2]", key_style),
+                Span::from(format!(": {}", tr!("hint.theme", tr!(theme().name)))),
             ]),
-            Spans::from(""),
             Spans::from(vec![
                 Span::styled("[F]", key_style),
-                Span::from(": Start a challenge (Get ready!)"),
+                Span::from(format!(": {}", tr!("keys.challenge"))),
+            ]),
+            Spans::from(vec![
+                Span::styled("[V]", key_style),
+                Span::from(format!(": {}", tr!("keys.history"))),
             ]),
-            Spans::from(""),
             Spans::from(vec![
-                Span::styled("[1]", key_style),
-                Span::from(": For AI team won race"),
+                Span::styled(race_keys(|keys| keys.0), key_style),
+                Span::from(format!(": {}", tr!("keys.race_for"))),
             ]),
             Spans::from(vec![
-                Span::styled("[0]", key_style),
-                Span::from(": Against AI team won race"),
+                Span::styled(race_keys(|keys| keys.1), key_style),
+                Span::from(format!(": {}", tr!("keys.race_against"))),
             ]),
         ];
         let col2 = vec![
             Spans::from(vec![
                 Span::styled("[A]", key_style),
-                Span::from(": Increase center capture for AI team"),
+                Span::from(format!(": {}", tr!("keys.center_add_for"))),
             ]),
             Spans::from(vec![
                 Span::styled("[O]", key_style),
-                Span::from(": Decrease center capture for AI team"),
+                Span::from(format!(": {}", tr!("keys.center_remove_for"))),
             ]),
             Spans::from(vec![
                 Span::styled("[S]", key_style),
-                Span::from(": Increase center capture for non-AI team"),
+                Span::from(format!(": {}", tr!("keys.center_add_against"))),
             ]),
             Spans::from(vec![
                 Span::styled("[N]", key_style),
-                Span::from(": Decrease center capture for non-AI team"),
+                Span::from(format!(": {}", tr!("keys.center_remove_against"))),
+            ]),
+            Spans::from(vec![
+                Span::styled(
+                    format!(
+                        "[{}]/[{}]",
+                        VPN_KEYS.0.to_ascii_uppercase(),
+                        VPN_KEYS.1.to_ascii_uppercase()
+                    ),
+                    key_style,
+                ),
+                Span::from(format!(": {}", tr!("keys.vpn"))),
             ]),
-            Spans::from(""),
             Spans::from(vec![
                 Span::styled("[5]", key_style),
-                Span::from(": Decrease points for AI team"),
+                Span::from(format!(": {}", tr!("keys.retreat_for"))),
             ]),
             Spans::from(vec![
                 Span::styled("[7]", key_style),
-                Span::from(": Decrease points for non-AI team"),
+                Span::from(format!(": {}", tr!("keys.retreat_against"))),
             ]),
         ];
         let col3 = vec![
             Spans::from(vec![
                 Span::styled("[E]", key_style),
-                Span::from(": Increase capture for AI team"),
+                Span::from(format!(": {}", tr!("keys.capture_add_for"))),
             ]),
             Spans::from(vec![
                 Span::styled("[U]", key_style),
-                Span::from(": Decrease capture for AI team"),
+                Span::from(format!(": {}", tr!("keys.capture_remove_for"))),
             ]),
             Spans::from(vec![
                 Span::styled("[T]", key_style),
-                Span::from(": Increase capture for non-AI team"),
+                Span::from(format!(": {}", tr!("keys.capture_add_against"))),
             ]),
             Spans::from(vec![
                 Span::styled("[H]", key_style),
-                Span::from(": Decrease capture for non-AI team"),
+                Span::from(format!(": {}", tr!("keys.capture_remove_against"))),
+            ]),
+            Spans::from(vec![
+                Span::styled("[Shift+E/U/T/H]", key_style),
+                Span::from(format!(": {}", tr!("keys.capture_other"))),
             ]),
-            Spans::from(""),
             Spans::from(vec![
                 Span::styled("[I]", key_style),
-                Span::from(": For AI team placed Database"),
+                Span::from(format!(
+                    ": {} {}",
+                    tr!("keys.database_for"),
+                    tr!("keys.supply", self.supply_left(&AiSide::For))
+                )),
             ]),
             Spans::from(vec![
                 Span::styled("[D]", key_style),
-                Span::from(": Against AI team placed Database"),
+                Span::from(format!(
+                    ": {} {}",
+                    tr!("keys.database_against"),
+                    tr!("keys.supply", self.supply_left(&AiSide::Against))
+                )),
             ]),
         ];
 
@@ -125,5 +175,43 @@ impl Game {
             Paragraph::new(col3).alignment(Alignment::Left),
             keys_cols[2],
         );
+
+        // Every line but exit can be clicked
+        let codes = [
+            [
+                None,
+                Some(Tab),
+                Some(F(2)),
+                Some(Char('f')),
+                Some(Char('v')),
+                Some(Char('1')),
+                Some(Char('0')),
+            ],
+            [
+                Some(Char('a')),
+                Some(Char('o')),
+                Some(Char('s')),
+                Some(Char('n')),
+                Some(Char(VPN_KEYS.0)),
+                Some(Char('5')),
+                Some(Char('7')),
+            ],
+            [
+                Some(Char('e')),
+                Some(Char('u')),
+                Some(Char('t')),
+                Some(Char('h')),
+                None,
+                Some(Char('i')),
+                Some(Char('d')),
+            ],
+        ];
+        for (col, codes) in codes.into_iter().enumerate() {
+            for (row, code) in codes.into_iter().enumerate() {
+                if let Some(code) = code {
+                    targets.add_row(keys_cols[col], row as u16, code);
+                }
+            }
+        }
     }
 }
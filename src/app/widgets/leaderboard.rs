@@ -0,0 +1,99 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+};
+
+use crate::app::{data::ratings::MAX_PLAYER_NAME, i18n::tr, theme::theme, App};
+
+impl App<'_> {
+    pub fn draw_leaderboard<B: Backend>(&mut self, f: &mut tui::Frame<B>, rect: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .vertical_margin(5)
+            .horizontal_margin(50)
+            .constraints([Constraint::Min(5), Constraint::Length(3)].as_ref())
+            .split(rect);
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let key_style = bold.fg(theme().key);
+
+        let leaderboard = self.ratings.leaderboard();
+        // Keep the last rows on screen when scrolling
+        let visible = chunks[0].height.saturating_sub(4) as usize;
+        self.scroll = self
+            .scroll
+            .min(leaderboard.len().saturating_sub(visible) as u16);
+
+        let rows: Vec<Row> = leaderboard
+            .iter()
+            .enumerate()
+            .skip(self.scroll as usize)
+            .map(|(rank, player)| {
+                Row::new(vec![
+                    Cell::from(format!("{}.", rank + 1)),
+                    Cell::from(player.name.clone()),
+                    Cell::from(Span::styled(format!("{:.0}", player.rating), bold)),
+                    Cell::from(player.games.to_string()),
+                    Cell::from(player.wins.to_string()),
+                    Cell::from(format!(
+                        "{:.0}%",
+                        player.wins as f64 * 100.0 / player.games.max(1) as f64
+                    )),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(rows)
+            .header(
+                Row::new(vec![
+                    "#",
+                    tr!("leaderboard.player"),
+                    tr!("leaderboard.rating"),
+                    tr!("leaderboard.games"),
+                    tr!("leaderboard.wins"),
+                    tr!("leaderboard.win_rate"),
+                ])
+                .style(bold.fg(theme().highlight))
+                .bottom_margin(1),
+            )
+            .block(
+                Block::default()
+                    .title(format!(" {} ", tr!("leaderboard.title")))
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().accent)),
+            )
+            .widths(&[
+                Constraint::Length(5),
+                Constraint::Min(MAX_PLAYER_NAME as u16),
+                Constraint::Length(8),
+                Constraint::Length(7),
+                Constraint::Length(6),
+                Constraint::Length(9),
+            ]);
+        f.render_widget(table, chunks[0]);
+
+        let mut lines = vec![Spans::from(vec![
+            Span::styled("[↑/↓]", key_style),
+            Span::from(format!(": {}    ", tr!("hint.scroll"))),
+            Span::styled("[Enter]", key_style),
+            Span::from(format!(": {}", tr!("hint.back"))),
+        ])];
+        if leaderboard.is_empty() {
+            lines.insert(0, Spans::from(tr!("leaderboard.empty")));
+        }
+        if let Some(err) = &self.ratings_error {
+            lines.push(Spans::from(Span::styled(
+                err.clone(),
+                Style::default().fg(theme().error),
+            )));
+        }
+        f.render_widget(
+            Paragraph::new(lines).alignment(Alignment::Center),
+            chunks[1],
+        );
+    }
+}
@@ -1,29 +1,70 @@
 pub mod capture;
 pub mod challenge;
+pub mod database;
+pub mod event;
 pub mod finished;
+pub mod history;
 pub mod keys;
+pub mod leaderboard;
 pub mod progress;
 pub mod race;
+pub mod report;
 pub mod setup;
 pub mod side;
+pub mod timeline;
 pub mod title;
+pub mod tournament;
+pub mod vpn;
 
 use tui::style::Color;
 
-use super::data::game::AiSide;
+use super::{
+    data::{game::AiSide, rules::GameMode},
+    i18n::tr,
+    theme::theme,
+};
 
 impl AiSide {
     pub fn color(&self) -> Color {
         match self {
-            AiSide::For => Color::Cyan,
-            AiSide::Against => Color::Red,
+            AiSide::For => theme().for_ai,
+            AiSide::Against => theme().against_ai,
+        }
+    }
+
+    // Name preceded by the team mark of the theme
+    pub fn label(&self) -> String {
+        format!("{}{}", self.mark(), self.name())
+    }
+
+    pub fn mark(&self) -> &'static str {
+        match self {
+            AiSide::For => theme().for_mark,
+            AiSide::Against => theme().against_mark,
+        }
+    }
+
+    pub fn fill(&self) -> &'static str {
+        match self {
+            AiSide::For => theme().for_fill,
+            AiSide::Against => theme().against_fill,
         }
     }
 
     pub fn name(&self) -> &str {
         match self {
-            AiSide::For => "For AI",
-            AiSide::Against => "Against AI",
+            AiSide::For => tr!("side.for"),
+            AiSide::Against => tr!("side.against"),
+        }
+    }
+}
+
+impl GameMode {
+    pub fn name(&self) -> String {
+        match self {
+            GameMode::Target(target) => tr!("mode.target", target),
+            GameMode::Rounds(rounds) => tr!("mode.rounds", rounds),
+            GameMode::Timed(minutes) => tr!("mode.timed", minutes),
         }
     }
 }
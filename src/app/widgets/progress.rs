@@ -1,38 +1,89 @@
 use tui::{
     backend::Backend,
+    buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Gauge},
+    widgets::{Block, Borders, Paragraph, Widget},
 };
 
-use crate::app::data::{
-    game::{AiSide, Game},
-    side::TOTAL_PROGRESS,
+use crate::app::{
+    data::{
+        game::{AiSide, Game},
+        race::SECS_IN_MIN,
+        rules::GameMode,
+    },
+    i18n::tr,
+    theme::theme,
 };
 
-fn gauge_from(game: &Game, ai_side: AiSide) -> Gauge {
+// Like tui's Gauge, but filled with the team pattern of the theme
+struct PatternGauge {
+    ratio: f64,
+    fill: &'static str,
+    color: Color,
+    label: String,
+}
+
+impl Widget for PatternGauge {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let filled = area.left() + (area.width as f64 * self.ratio).round() as u16;
+        let background = theme().background;
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                cell.set_bg(background).set_fg(self.color);
+                cell.set_symbol(if x < filled { self.fill } else { " " });
+            }
+        }
+
+        let label_width = self.label.chars().count() as u16;
+        let y = area.top() + area.height / 2;
+        let start = area.left() + area.width.saturating_sub(label_width) / 2;
+        for (i, c) in self.label.chars().enumerate() {
+            let x = start + i as u16;
+            if x >= area.right() {
+                break;
+            }
+            let style = if x < filled {
+                Style::default().fg(background).bg(self.color)
+            } else {
+                Style::default().fg(self.color).bg(background)
+            };
+            buf.get_mut(x, y)
+                .set_symbol(&c.to_string())
+                .set_style(style.add_modifier(Modifier::BOLD));
+        }
+    }
+}
+
+fn gauge_from(game: &Game, ai_side: AiSide) -> PatternGauge {
     let progress = (match ai_side {
         AiSide::For => game.for_ai.as_ref().unwrap().progress(),
         AiSide::Against => game.against_ai.as_ref().unwrap().progress(),
     } as f64)
-        / (TOTAL_PROGRESS as f64);
-    let label = format!("{:.2}%", progress * 100.0);
-    Gauge::default()
-        .gauge_style(
-            Style::default()
-                .fg(ai_side.color())
-                .bg(Color::Black)
-                .add_modifier(Modifier::BOLD),
-        )
-        .label(label)
-        .ratio(progress)
+        / (game.rules.mode.goal() as f64);
+    let progress = progress.min(1.0);
+    if game.is_blackout() {
+        return PatternGauge {
+            ratio: 0.0,
+            fill: ai_side.fill(),
+            color: ai_side.color(),
+            label: format!("{}???", ai_side.mark()),
+        };
+    }
+    PatternGauge {
+        ratio: progress,
+        fill: ai_side.fill(),
+        color: ai_side.color(),
+        label: format!("{}{:.2}%", ai_side.mark(), progress * 100.0),
+    }
 }
 
 impl Game {
     pub fn draw_progress<B: Backend>(&self, f: &mut tui::Frame<B>, rect: Rect) {
         let block = Block::default()
-            .title(" Progress ")
-            .style(Style::default().fg(Color::Yellow))
+            .title(format!(" {} ", tr!("progress.title")))
+            .style(Style::default().fg(theme().highlight))
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL);
         f.render_widget(block, rect);
@@ -54,5 +105,35 @@ impl Game {
 
         f.render_widget(gauge_from(self, AiSide::For), progress_chunks[1]);
         f.render_widget(gauge_from(self, AiSide::Against), progress_chunks[3]);
+
+        let status = match self.rules.mode {
+            GameMode::Target(target) => tr!("progress.target", target),
+            GameMode::Rounds(rounds) => {
+                let played = self
+                    .for_ai
+                    .as_ref()
+                    .unwrap()
+                    .nb_rounds
+                    .min(self.against_ai.as_ref().unwrap().nb_rounds);
+                tr!("progress.round", (played + 1).min(rounds), rounds)
+            }
+            GameMode::Timed(_) => {
+                let remaining = self.remaining_time().unwrap_or_else(chrono::Duration::zero);
+                tr!(
+                    "progress.time_left",
+                    format!(
+                        "{}:{:02}",
+                        remaining.num_minutes(),
+                        remaining.num_seconds() as u64 % SECS_IN_MIN
+                    )
+                )
+            }
+        };
+        f.render_widget(
+            Paragraph::new(status)
+                .style(Style::default().fg(theme().text))
+                .alignment(Alignment::Center),
+            progress_chunks[4],
+        );
     }
 }
@@ -1,3 +1,4 @@
+use crossterm::event::KeyCode;
 use tui::{
     backend::Backend,
     layout::{
@@ -5,77 +6,183 @@ use tui::{
         Direction::{self, Horizontal},
         Layout, Rect,
     },
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::app::data::{
-    game::Game,
-    race::{self, SECS_IN_MIN},
+use crate::app::{
+    data::{
+        game::{AiSide, Game},
+        race::{self, SECS_IN_MIN},
+    },
+    i18n::{self, tr},
+    mouse::Targets,
+    theme::theme,
 };
 
+const TIMER_WIDTH: u16 = 12;
+const FAIRNESS_WIDTH: u16 = 20;
+const RECENT_RACES: usize = 3;
+
+// Claim keys of each running race, oldest first
+pub const RACE_KEYS: [(char, char); race::MAX_RACES] = [('1', '0'), ('2', '9'), ('3', '8')];
+
+fn minutes(secs: u64) -> String {
+    format!("{}:{:02}", secs / SECS_IN_MIN, secs % SECS_IN_MIN)
+}
+
 impl Game {
-    pub fn draw_race<B: Backend>(&self, f: &mut tui::Frame<B>, rect: Rect) {
+    pub fn draw_race<B: Backend>(&self, f: &mut tui::Frame<B>, rect: Rect, targets: &mut Targets) {
         let marged = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(100)])
-            .horizontal_margin(45)
+            .horizontal_margin(30)
             .split(rect)[0];
 
         let mut race_block = Block::default()
-            .title(" Race ")
+            .title(format!(" {} ", tr!("race.title")))
             .title_alignment(Alignment::Left)
             .borders(Borders::ALL);
-        if let Some(_) = &self.race {
-            race_block = race_block.style(Style::default().fg(Color::Magenta));
+        if !self.races.is_empty() {
+            race_block = race_block.style(Style::default().fg(theme().accent));
         };
+        let inner = race_block.inner(marged);
         f.render_widget(race_block, marged);
 
-        let chunks = Layout::default()
-            .direction(Horizontal)
-            .constraints(if let Some(_) = &self.race {
-                [Constraint::Ratio(3, 4), Constraint::Ratio(1, 4)]
-            } else {
-                [Constraint::Ratio(4, 4), Constraint::Ratio(0, 4)]
-            })
-            .margin(1)
-            .split(marged);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(self.rules.max_races as u16),
+                    Constraint::Length(1), // Recent races
+                ]
+                .as_ref(),
+            )
+            .split(inner);
 
-        let text = match &self.race {
-            Some(race) => vec![
-                Span::from("Rush now to "),
+        if self.races.is_empty() {
+            f.render_widget(
+                Paragraph::new(tr!("race.none")).alignment(Alignment::Center),
+                rows[0],
+            );
+        }
+
+        for (i, (race, (for_key, against_key))) in self.races.iter().zip(RACE_KEYS).enumerate() {
+            let row = Rect {
+                y: rows[0].y + i as u16,
+                height: 1,
+                ..rows[0]
+            };
+            // Award buttons on both ends
+            let for_award = format!("[{} {}]", for_key, AiSide::For.label());
+            let against_award = format!("[{} {}]", AiSide::Against.label(), against_key);
+            let chunks = Layout::default()
+                .direction(Horizontal)
+                .constraints([
+                    Constraint::Length(for_award.chars().count() as u16),
+                    Constraint::Min(0),
+                    Constraint::Length(FAIRNESS_WIDTH),
+                    Constraint::Length(TIMER_WIDTH),
+                    Constraint::Length(against_award.chars().count() as u16),
+                ])
+                .split(row);
+
+            let (before, after) = i18n::split(tr!("race.rush"));
+            let text = vec![
+                Span::from(before),
                 Span::styled(
                     race.position.to_string(),
                     Style::default()
                         .add_modifier(Modifier::BOLD)
-                        .fg(Color::Yellow),
+                        .fg(theme().highlight),
                 ),
-                Span::from(format!(
-                    " ! First team there gains {} points.",
-                    race::RACE_POINTS,
-                )),
-            ],
-            None => vec![Span::styled("No race for the moment", Style::default())],
-        };
-
-        let paragraph = Paragraph::new(Spans::from(text)).alignment(Alignment::Center);
+                Span::from(i18n::fill(after, &[&race.reward()])),
+            ];
+            f.render_widget(
+                Paragraph::new(Spans::from(text)).alignment(Alignment::Center),
+                chunks[1],
+            );
 
-        f.render_widget(paragraph, chunks[0]);
+            // How far each team is from the target
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    tr!(
+                        "race.fairness",
+                        race.fairness(),
+                        race.distances.0,
+                        race.distances.1
+                    ),
+                    Style::default().fg(theme().key),
+                ))
+                .alignment(Alignment::Center),
+                chunks[2],
+            );
 
-        if let Some(race) = &self.race {
             let remaining = race.remaining_time();
-            let text = format!(
-                " {}:{} ",
-                remaining.num_minutes(),
-                remaining.num_seconds() as u64 % SECS_IN_MIN
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    format!(" {} ", minutes(remaining.num_seconds().max(0) as u64)),
+                    Style::default().bg(theme().accent).fg(theme().background),
+                ))
+                .alignment(Alignment::Center),
+                chunks[3],
             );
-            let paragraph = Paragraph::new(Span::styled(
-                text,
-                Style::default().bg(Color::Magenta).fg(Color::Black),
-            ))
-            .alignment(Alignment::Center);
-            f.render_widget(paragraph, chunks[1]);
+
+            for (ai_side, award, chunk, key) in [
+                (AiSide::For, for_award, chunks[0], for_key),
+                (AiSide::Against, against_award, chunks[4], against_key),
+            ] {
+                f.render_widget(
+                    Paragraph::new(Span::styled(
+                        award,
+                        Style::default()
+                            .add_modifier(Modifier::BOLD)
+                            .bg(ai_side.color())
+                            .fg(theme().background),
+                    )),
+                    chunk,
+                );
+                targets.add(chunk, KeyCode::Char(key));
+            }
+        }
+
+        // Latest results first
+        let mut recent = vec![Span::from(format!("{} ", tr!("race.recent")))];
+        for result in self.race_history.iter().rev().take(RECENT_RACES) {
+            recent.push(Span::styled(
+                result.position.to_string(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+            recent.push(match &result.winner {
+                Some(ai_side) => Span::styled(
+                    format!(
+                        " {}{} ",
+                        ai_side.mark(),
+                        tr!(
+                            "race.won",
+                            ai_side.name(),
+                            result.points,
+                            minutes(result.took.as_secs())
+                        )
+                    ),
+                    Style::default().fg(ai_side.color()),
+                ),
+                None => Span::styled(
+                    format!(" {} ", tr!("race.missed", result.points)),
+                    Style::default().fg(theme().error),
+                ),
+            });
+            recent.push(Span::from("  "));
+        }
+        if self.race_history.is_empty() {
+            recent.push(Span::from("-"));
         }
+        f.render_widget(
+            Paragraph::new(Spans::from(recent))
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(theme().text)),
+            rows[1],
+        );
     }
 }
@@ -0,0 +1,204 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, Wrap},
+};
+
+use crate::app::{
+    data::{
+        game::{AiSide, Game},
+        score::{Source, SOURCES},
+        timeline::{challenge_record, deepest_deficit, largest_lead, lead_changes},
+    },
+    i18n::tr,
+    theme::theme,
+};
+
+const TEAMS: [AiSide; 2] = [AiSide::For, AiSide::Against];
+
+impl Source {
+    pub fn name(&self) -> &str {
+        match self {
+            Source::Handicap => tr!("report.handicap"),
+            Source::Captures => tr!("report.captures"),
+            Source::Center => tr!("report.center"),
+            Source::Races => tr!("report.races"),
+            Source::Databases => tr!("report.databases"),
+            Source::Challenges => tr!("report.challenges"),
+            Source::Events => tr!("report.events"),
+            Source::Penalties => tr!("report.penalties"),
+        }
+    }
+}
+
+impl Game {
+    // Where the points came from and how the lead went back and forth
+    pub fn draw_report<B: Backend>(
+        &self,
+        f: &mut tui::Frame<B>,
+        rect: Rect,
+        winner: Option<&AiSide>,
+    ) {
+        let block = Block::default()
+            .title(format!(" {} ", tr!("report.title")))
+            .style(Style::default().fg(theme().highlight))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Length(3), // Duration and rounds
+                    Constraint::Min(0),    // Points by source
+                    Constraint::Length(6), // Lead, comeback and challenges
+                ]
+                .as_ref(),
+            )
+            .horizontal_margin(1)
+            .split(block.inner(rect));
+        f.render_widget(block, rect);
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let text = Style::default().fg(theme().text);
+        let team = |ai_side: &AiSide| {
+            Span::styled(
+                format!(
+                    "{}{}",
+                    ai_side.mark(),
+                    self.team_name(ai_side).unwrap_or(ai_side.name())
+                ),
+                Style::default()
+                    .fg(ai_side.color())
+                    .add_modifier(Modifier::BOLD),
+            )
+        };
+
+        let duration = self.duration().unwrap_or_default().as_secs();
+        let rounds = |ai_side: &AiSide| match ai_side {
+            AiSide::For => self.for_ai.as_ref().map_or(0, |side| side.nb_rounds),
+            AiSide::Against => self.against_ai.as_ref().map_or(0, |side| side.nb_rounds),
+        };
+        let summary = vec![
+            Spans::from(vec![
+                Span::from(format!("{} ", tr!("report.duration"))),
+                Span::styled(format!("{}:{:02}", duration / 60, duration % 60), bold),
+            ]),
+            Spans::from(vec![
+                Span::from(format!("{} ", tr!("side.nb_rounds"))),
+                team(&AiSide::For),
+                Span::styled(format!(" {}  ", rounds(&AiSide::For)), bold),
+                team(&AiSide::Against),
+                Span::styled(format!(" {}", rounds(&AiSide::Against)), bold),
+            ]),
+        ];
+        f.render_widget(
+            Paragraph::new(summary)
+                .style(text)
+                .alignment(Alignment::Center),
+            chunks[0],
+        );
+
+        // Sources that gave nothing to either team are left out
+        let points = |source: &Source, ai_side: &AiSide| {
+            let points = self.points_from(ai_side, *source);
+            Cell::from(if points > 0 {
+                format!("+{}", points)
+            } else {
+                points.to_string()
+            })
+        };
+        let mut rows: Vec<Row> = SOURCES
+            .iter()
+            .filter(|source| {
+                TEAMS
+                    .iter()
+                    .any(|ai_side| self.points_from(ai_side, **source) != 0)
+            })
+            .map(|source| {
+                Row::new(vec![
+                    Cell::from(source.name()),
+                    points(source, &AiSide::For),
+                    points(source, &AiSide::Against),
+                ])
+                .style(text)
+            })
+            .collect();
+        let total = |ai_side: &AiSide| {
+            let total: i64 = SOURCES
+                .iter()
+                .map(|source| self.points_from(ai_side, *source))
+                .sum();
+            Cell::from(total.to_string())
+        };
+        rows.push(
+            Row::new(vec![
+                Cell::from(tr!("report.total")),
+                total(&AiSide::For),
+                total(&AiSide::Against),
+            ])
+            .style(bold.fg(theme().highlight)),
+        );
+        let table = Table::new(rows)
+            .header(
+                Row::new(vec![
+                    Cell::from(tr!("report.source")),
+                    Cell::from(team(&AiSide::For)),
+                    Cell::from(team(&AiSide::Against)),
+                ])
+                .style(bold.fg(theme().highlight))
+                .bottom_margin(1),
+            )
+            .widths(&[
+                Constraint::Percentage(40),
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+            ]);
+        f.render_widget(table, chunks[1]);
+
+        let mut lines = vec![];
+        lines.push(Spans::from(match largest_lead(&self.timeline) {
+            Some((ai_side, lead, turn)) => vec![
+                Span::from(format!("{} ", tr!("report.largest_lead"))),
+                team(&ai_side),
+                Span::from(format!(" {}", tr!("report.lead", lead, turn))),
+            ],
+            None => vec![Span::from(tr!("report.no_lead"))],
+        }));
+        let changes = lead_changes(&self.timeline);
+        lines.push(Spans::from(if changes.is_empty() {
+            tr!("report.no_lead_change").to_string()
+        } else {
+            let turns: Vec<String> = changes.iter().map(|(turn, _)| turn.to_string()).collect();
+            tr!("report.lead_changes", turns.join(", "))
+        }));
+        // Only the winner's comeback is worth telling
+        lines.push(Spans::from(
+            match winner.map(|ai_side| (deepest_deficit(&self.timeline, ai_side), ai_side)) {
+                Some((deficit, ai_side)) if deficit > 0 => vec![
+                    team(ai_side),
+                    Span::from(format!(" {}", tr!("report.comeback", deficit))),
+                ],
+                _ => vec![Span::from(tr!("report.no_comeback"))],
+            },
+        ));
+        let (for_ai, against_ai, undecided) = challenge_record(&self.timeline);
+        lines.push(Spans::from(vec![
+            Span::from(format!("{} ", tr!("report.challenge_record"))),
+            team(&AiSide::For),
+            Span::styled(format!(" {}  ", for_ai), bold),
+            team(&AiSide::Against),
+            Span::styled(format!(" {}  ", against_ai), bold),
+            Span::from(tr!("report.undecided", undecided)),
+        ]));
+        f.render_widget(
+            Paragraph::new(lines)
+                .style(text)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+            chunks[2],
+        );
+    }
+}
@@ -1,28 +1,169 @@
+use itertools::Itertools;
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
 };
 
 use crate::app::{
-    data::game::{AiSide, MAX_PLAYERS, MIN_PLAYERS},
+    data::{
+        board::{Board, Feature, FEATURES},
+        capture::{Location, Transfer},
+        challenge::{self, Category, TieRule},
+        event::EventRate,
+        game::{AiSide, PlacementError, MAX_PLAYERS, MIN_PLAYERS},
+        handicap::{HandicapField, HANDICAP_FIELDS},
+        race::RaceBalance,
+        venue::{Intensity, Requirement, REQUIREMENTS},
+    },
+    i18n::tr,
+    sheet::{SHEET_EXTENSIONS, SHEET_FILE},
+    theme::theme,
     App, AppState,
 };
 
+impl Feature {
+    fn label(&self) -> String {
+        match self {
+            Feature::Vpn(ai_side) | Feature::Capture(Location::Home(ai_side)) => ai_side.label(),
+            Feature::Capture(Location::Center) => tr!("capture.center").to_string(),
+        }
+    }
+}
+
+impl PlacementError {
+    pub fn description(&self) -> &str {
+        match self {
+            PlacementError::Position(err) => err.description(),
+            PlacementError::Illegal => tr!("placement.illegal"),
+            PlacementError::WrongHalf => tr!("placement.wrong_half"),
+            PlacementError::WrongColumn => tr!("placement.wrong_column"),
+            PlacementError::Taken => tr!("placement.taken"),
+            PlacementError::NoRoom => tr!("placement.no_room"),
+            PlacementError::Code => tr!("placement.code"),
+        }
+    }
+}
+
+impl Requirement {
+    pub fn name(&self) -> &str {
+        match self {
+            Requirement::Water => tr!("venue.water"),
+            Requirement::Paper => tr!("venue.paper"),
+            Requirement::JugglingItems => tr!("venue.juggling_items"),
+            Requirement::SprintSpace => tr!("venue.sprint_space"),
+            Requirement::Indoors => tr!("venue.indoors"),
+        }
+    }
+}
+
+impl Category {
+    pub fn name(&self) -> &str {
+        match self {
+            Category::Duel => tr!("venue.duels"),
+            Category::Team => tr!("venue.team_challenges"),
+        }
+    }
+}
+
+impl TieRule {
+    pub fn name(&self) -> &str {
+        match self {
+            TieRule::Draw => tr!("tie.draw"),
+            TieRule::Referee => tr!("tie.referee"),
+            TieRule::Underdog => tr!("tie.underdog"),
+        }
+    }
+}
+
+impl RaceBalance {
+    pub fn name(&self) -> &str {
+        match self {
+            RaceBalance::Off => tr!("balance.off"),
+            RaceBalance::Manhattan => tr!("balance.manhattan"),
+            RaceBalance::Path => tr!("balance.path"),
+        }
+    }
+}
+
+impl EventRate {
+    pub fn name(&self) -> &str {
+        match self {
+            EventRate::Off => tr!("rate.off"),
+            EventRate::Rare => tr!("rate.rare"),
+            EventRate::Normal => tr!("rate.normal"),
+            EventRate::Frequent => tr!("rate.frequent"),
+        }
+    }
+}
+
+impl Transfer {
+    pub fn name(&self) -> &str {
+        match self {
+            Transfer::Majority => tr!("transfer.majority"),
+            Transfer::Exclusive => tr!("transfer.exclusive"),
+        }
+    }
+}
+
+impl Intensity {
+    pub fn name(&self) -> &str {
+        match self {
+            Intensity::Calm => tr!("venue.calm"),
+            Intensity::Active => tr!("venue.active"),
+            Intensity::Physical => tr!("venue.physical"),
+        }
+    }
+}
+
 impl App<'_> {
+    // Labelled positions of a step, the picked one highlighted or replaced by the typed one
+    fn position_spans(
+        &self,
+        step: bool,
+        board: &Board,
+        features: &[Feature],
+    ) -> Vec<Span<'static>> {
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let mut spans = vec![];
+        for (i, feature) in features.iter().enumerate() {
+            let position = board
+                .get(feature)
+                .map_or("?".to_string(), |position| position.to_string());
+            spans.push(Span::from(format!("{} ", feature.label())));
+            spans.push(if !step || i != self.position_field {
+                Span::styled(position, bold)
+            } else if self.entering_position {
+                Span::styled(
+                    format!("{}_", self.input),
+                    bold.bg(theme().key).fg(theme().background),
+                )
+            } else {
+                Span::styled(position, bold.bg(theme().highlight).fg(theme().background))
+            });
+            spans.push(Span::from("  "));
+        }
+        spans.pop();
+        spans
+    }
+
     pub fn draw_setup<B: Backend>(&mut self, f: &mut tui::Frame<B>, rect: Rect, ai_side: AiSide) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .margin(20)
+            .vertical_margin(6)
             .horizontal_margin(60)
             .constraints(
                 [
                     Constraint::Length(3), // For ai
                     Constraint::Length(3), // Against ai
-                    Constraint::Length(3), // VPN positions
-                    Constraint::Length(3), // Capture positions
+                    Constraint::Length(6), // Roster
+                    Constraint::Length(8), // Game mode
+                    Constraint::Length(5), // Venue
+                    Constraint::Length(5), // Handicaps
+                    Constraint::Length(4), // VPN positions and rules
+                    Constraint::Length(5), // Capture positions, board constraints and setup code
                     Constraint::Min(2),
                 ]
                 .as_ref(),
@@ -31,18 +172,20 @@ impl App<'_> {
 
         let white = Style::default()
             .add_modifier(Modifier::ITALIC)
-            .fg(tui::style::Color::White);
+            .fg(theme().text);
 
         let team_block = Block::default()
             .title_alignment(Alignment::Left)
             .borders(Borders::ALL);
 
-        let for_ai_block = team_block
-            .clone()
-            .title(format!(" {} ", AiSide::For.name()));
-        let against_ai_block = team_block.title(format!(" {} ", AiSide::Against.name()));
+        let team_title = |ai_side: AiSide| match self.game.team_name(&ai_side) {
+            Some(team) => format!(" {} - {} ", ai_side.name(), team),
+            None => format!(" {} ", ai_side.name()),
+        };
+        let for_ai_block = team_block.clone().title(team_title(AiSide::For));
+        let against_ai_block = team_block.title(team_title(AiSide::Against));
 
-        let nb_player_pr = format!("Enter digit between {} and {}", MIN_PLAYERS, MAX_PLAYERS);
+        let nb_player_pr = tr!("setup.digit", MIN_PLAYERS, MAX_PLAYERS);
 
         let for_ai_nb = match self.game.for_ai.as_ref() {
             Some(side) => Span::styled(
@@ -60,7 +203,7 @@ impl App<'_> {
             None => Span::styled(
                 match (ai_side, self.game.for_ai.as_ref()) {
                     (AiSide::For, None) => "...".to_string(),
-                    (AiSide::For, Some(_)) => "Press Enter for next".to_string(),
+                    (AiSide::For, Some(_)) => tr!("setup.press_next").to_string(),
                     (AiSide::Against, _) => nb_player_pr,
                 },
                 white,
@@ -68,13 +211,13 @@ impl App<'_> {
         };
 
         let for_ai_nb_player_prompt = Paragraph::new(Spans::from(vec![
-            Span::from(" Number of players: "),
+            Span::from(format!(" {} ", tr!("setup.nb_players"))),
             for_ai_nb,
         ]))
         .block(for_ai_block.style(Style::default().fg(AiSide::For.color())));
 
         let against_ai_nb_player_prompt = Paragraph::new(Spans::from(vec![
-            Span::from(" Number of players: "),
+            Span::from(format!(" {} ", tr!("setup.nb_players"))),
             against_ai_nb,
         ]))
         .block(against_ai_block.style(Style::default().fg(AiSide::Against.color())));
@@ -82,62 +225,561 @@ impl App<'_> {
         f.render_widget(for_ai_nb_player_prompt, chunks[0]);
         f.render_widget(against_ai_nb_player_prompt, chunks[1]);
 
-        let vpn_pos = match (
-            &self.state,
-            self.game.against_ai.as_ref(),
-            &self.vpn_positions,
-        ) {
-            (AppState::PlayerInput(_), None, _) => Span::styled("...", white),
-            (AppState::PlayerInput(_), Some(_), None) => {
-                Span::styled(" Press Enter to generate VPN postions", white)
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let key_style = bold.fg(theme().key);
+
+        let roster_text = match (&self.state, &self.game.for_ai, &self.game.against_ai) {
+            (AppState::PlayerInput(_), _, _) | (_, None, _) | (_, _, None) => {
+                vec![Spans::from(Span::styled(" ...", white))]
             }
-            (_, _, Some((p1, p2))) => Span::styled(
-                format!("{} {}", p1.to_string(), p2.to_string()),
-                Style::default().add_modifier(Modifier::BOLD),
+            (_, Some(for_ai), Some(against_ai)) => {
+                let editing = matches!(self.state, AppState::RosterInput);
+                let mut slot = 0;
+                let mut lines: Vec<Spans> = [(AiSide::For, for_ai), (AiSide::Against, against_ai)]
+                    .into_iter()
+                    .map(|(ai_side, side)| {
+                        let mut spans = vec![Span::styled(
+                            format!(" {:<12}", ai_side.name()),
+                            Style::default().fg(ai_side.color()),
+                        )];
+                        for name in &side.players {
+                            let selected = editing && slot == self.roster_slot;
+                            slot += 1;
+                            let text = match (name.is_empty(), selected) {
+                                (true, true) => "_".to_string(),
+                                (true, false) => "?".to_string(),
+                                (false, true) => format!("{}_", name),
+                                (false, false) => format!(
+                                    "{} ({:.0})",
+                                    name.trim(),
+                                    self.ratings.rating(name.trim())
+                                ),
+                            };
+                            spans.push(Span::styled(
+                                format!("[{}]", text),
+                                if selected {
+                                    bold.add_modifier(Modifier::REVERSED)
+                                } else {
+                                    bold
+                                },
+                            ));
+                            spans.push(Span::from(" "));
+                        }
+                        Spans::from(spans)
+                    })
+                    .collect();
+
+                lines.push(match (for_ai.roster(), against_ai.roster()) {
+                    (Some(for_names), Some(against_names)) => {
+                        let players = [for_names.clone(), against_names.clone()].concat();
+                        let (balanced, _) = self.ratings.balance(&players, for_names.len());
+                        let gap = self.ratings.rating_gap(&for_names, &against_names);
+                        let mut spans = vec![Span::from(format!(
+                            " {}",
+                            tr!("setup.rating_gap", format!("{:+.0}", gap))
+                        ))];
+                        if balanced.iter().sorted().ne(for_names.iter().sorted()) {
+                            spans.push(Span::from(format!(
+                                "    {}",
+                                tr!("setup.suggested", AiSide::For.name(), balanced.join(", "))
+                            )));
+                        }
+                        Spans::from(spans)
+                    }
+                    _ => Spans::from(Span::styled(
+                        format!(" {}", tr!("setup.name_players")),
+                        white,
+                    )),
+                });
+
+                if editing {
+                    lines.push(Spans::from(vec![
+                        Span::styled(" [↑/↓]", key_style),
+                        Span::styled(format!(": {}  ", tr!("hint.select_player")), white),
+                        Span::styled("[Tab]", key_style),
+                        Span::styled(format!(": {}  ", tr!("hint.balance")), white),
+                        Span::styled("[Enter]", key_style),
+                        Span::styled(format!(": {}", tr!("hint.next")), white),
+                    ]));
+                }
+                lines
+            }
+        };
+
+        let roster_block = Block::default()
+            .title(format!(" {} ", tr!("setup.players")))
+            .title_alignment(Alignment::Left)
+            .borders(Borders::ALL);
+
+        f.render_widget(
+            Paragraph::new(roster_text)
+                .block(roster_block)
+                .style(Style::default().fg(theme().text)),
+            chunks[2],
+        );
+
+        let mode_text = match self.state {
+            AppState::PlayerInput(_) | AppState::RosterInput => {
+                vec![Spans::from(Span::styled(" ...", white))]
+            }
+            _ => vec![
+                Spans::from(vec![
+                    Span::from(format!(" {} ", tr!("setup.mode"))),
+                    Span::styled(self.game.rules.mode.name(), bold),
+                    Span::from(format!("    {} ", tr!("setup.sudden_death"))),
+                    Span::styled(
+                        if self.game.rules.sudden_death {
+                            tr!("setup.on")
+                        } else {
+                            tr!("setup.off")
+                        },
+                        bold,
+                    ),
+                ]),
+                Spans::from(vec![
+                    Span::from(format!(" {} ", tr!("setup.tie_rule"))),
+                    Span::styled(self.game.rules.tie_rule.name(), bold),
+                    if let AppState::ModeInput = self.state {
+                        Span::styled("  [J]", key_style)
+                    } else {
+                        Span::from("")
+                    },
+                ]),
+                Spans::from(vec![
+                    Span::from(format!(" {} ", tr!("setup.races"))),
+                    Span::styled(self.game.rules.race_balance.name(), bold),
+                    if let AppState::ModeInput = self.state {
+                        Span::styled("  [R]", key_style)
+                    } else {
+                        Span::from("")
+                    },
+                    Span::from(format!("    {} ", tr!("setup.max_races"))),
+                    Span::styled(self.game.rules.max_races.to_string(), bold),
+                    if let AppState::ModeInput = self.state {
+                        Span::styled("  [N]", key_style)
+                    } else {
+                        Span::from("")
+                    },
+                ]),
+                Spans::from(vec![
+                    Span::from(format!(" {} ", tr!("setup.lucy_events"))),
+                    Span::styled(self.game.rules.event_rate.name(), bold),
+                    if let AppState::ModeInput = self.state {
+                        Span::styled("  [L]", key_style)
+                    } else {
+                        Span::from("")
+                    },
+                    Span::from(format!("    {} ", tr!("setup.capture_transfer"))),
+                    Span::styled(self.game.rules.capture_transfer.name(), bold),
+                    if let AppState::ModeInput = self.state {
+                        Span::styled("  [C]", key_style)
+                    } else {
+                        Span::from("")
+                    },
+                ]),
+                Spans::from(vec![
+                    Span::from(format!(" {} ", tr!("setup.database_supply"))),
+                    Span::styled(self.game.rules.database_supply.to_string(), bold),
+                    if let AppState::ModeInput = self.state {
+                        Span::styled("  [D]", key_style)
+                    } else {
+                        Span::from("")
+                    },
+                    Span::from(format!("    {} ", tr!("setup.raid"))),
+                    Span::styled(self.game.rules.raid.name(), bold),
+                    if let AppState::ModeInput = self.state {
+                        Span::styled("  [S]", key_style)
+                    } else {
+                        Span::from("")
+                    },
+                ]),
+                if let AppState::ModeInput = self.state {
+                    Spans::from(vec![
+                        Span::styled(" [←/→]", key_style),
+                        Span::styled(format!(": {}  ", tr!("hint.change_mode")), white),
+                        Span::styled("[↑/↓]", key_style),
+                        Span::styled(format!(": {}  ", tr!("hint.adjust")), white),
+                        Span::styled("[X]", key_style),
+                        Span::styled(format!(": {}  ", tr!("hint.sudden_death")), white),
+                        Span::styled("[Enter]", key_style),
+                        Span::styled(format!(": {}", tr!("hint.next")), white),
+                    ])
+                } else {
+                    Spans::from("")
+                },
+            ],
+        };
+
+        let mode_block = Block::default()
+            .title(format!(" {} ", tr!("setup.game_mode")))
+            .title_alignment(Alignment::Left)
+            .borders(Borders::ALL);
+
+        f.render_widget(
+            Paragraph::new(mode_text)
+                .block(mode_block)
+                .style(Style::default().fg(theme().success)),
+            chunks[3],
+        );
+
+        let venue = &self.game.rules.venue;
+        let venue_text = match self.state {
+            AppState::PlayerInput(_) | AppState::RosterInput | AppState::ModeInput => {
+                vec![Spans::from(Span::styled(" ...", white))]
+            }
+            _ => {
+                let editing = matches!(self.state, AppState::VenueInput);
+                let field_style = |field: usize| {
+                    if editing && field == self.venue_field {
+                        bold.add_modifier(Modifier::REVERSED)
+                    } else {
+                        bold
+                    }
+                };
+                let mut toggles = vec![Span::from(" ")];
+                for (field, requirement) in REQUIREMENTS.iter().enumerate() {
+                    toggles.push(Span::styled(
+                        format!(
+                            "[{}] {}",
+                            if venue.has(*requirement) { "x" } else { " " },
+                            requirement.name()
+                        ),
+                        field_style(field),
+                    ));
+                    toggles.push(Span::from("  "));
+                }
+                let mut lines = vec![
+                    Spans::from(toggles),
+                    Spans::from(vec![
+                        Span::from(format!(" {} ", tr!("setup.max_intensity"))),
+                        Span::styled(venue.max_intensity.name(), field_style(REQUIREMENTS.len())),
+                        Span::from(format!(
+                            "    {}",
+                            tr!("setup.playable", challenge::playable(venue, true).len())
+                        )),
+                    ]),
+                ];
+                if editing {
+                    lines.push(Spans::from(vec![
+                        Span::styled(" [←/→]", key_style),
+                        Span::styled(format!(": {}  ", tr!("hint.select")), white),
+                        Span::styled("[Space]", key_style),
+                        Span::styled(format!(": {}  ", tr!("hint.toggle")), white),
+                        Span::styled("[Enter]", key_style),
+                        Span::styled(format!(": {}", tr!("hint.next")), white),
+                    ]));
+                }
+                lines
+            }
+        };
+
+        let venue_block = Block::default()
+            .title(format!(" {} ", tr!("setup.venue")))
+            .title_alignment(Alignment::Left)
+            .borders(Borders::ALL);
+
+        f.render_widget(
+            Paragraph::new(venue_text)
+                .block(venue_block)
+                .style(Style::default().fg(theme().text)),
+            chunks[4],
+        );
+
+        let editing = matches!(self.state, AppState::HandicapInput);
+        let field_span = |ai_side: &AiSide, field: HandicapField, text: String| {
+            let selected =
+                editing && HANDICAP_FIELDS[self.handicap_field] == (ai_side.clone(), field);
+            Span::styled(
+                text,
+                if selected {
+                    bold.add_modifier(Modifier::REVERSED)
+                } else {
+                    bold
+                },
+            )
+        };
+
+        let handicap_text = match (&self.state, &self.game.for_ai, &self.game.against_ai) {
+            (
+                AppState::HandicapInput | AppState::VPNPositions | AppState::CapturePositions,
+                Some(for_ai),
+                Some(against_ai),
+            ) => {
+                let mut lines: Vec<Spans> = [(AiSide::For, for_ai), (AiSide::Against, against_ai)]
+                    .into_iter()
+                    .map(|(ai_side, side)| {
+                        Spans::from(vec![
+                            Span::styled(
+                                format!(" {:<12}", ai_side.name()),
+                                Style::default().fg(ai_side.color()),
+                            ),
+                            Span::from(format!("{} ", tr!("setup.start_progress"))),
+                            field_span(
+                                &ai_side,
+                                HandicapField::Progress,
+                                format!("+{}", side.handicap.progress),
+                            ),
+                            Span::from(format!("  {} ", tr!("setup.start_captures"))),
+                            field_span(
+                                &ai_side,
+                                HandicapField::Captures,
+                                format!("+{}", side.handicap.captures),
+                            ),
+                            Span::from(format!("  {} ", tr!("setup.capture_points"))),
+                            field_span(
+                                &ai_side,
+                                HandicapField::Multiplier,
+                                format!("x{:.2}", side.handicap.multiplier),
+                            ),
+                        ])
+                    })
+                    .collect();
+                if editing {
+                    lines.push(Spans::from(vec![
+                        Span::styled(" [↑/↓]", key_style),
+                        Span::styled(format!(": {}  ", tr!("hint.select")), white),
+                        Span::styled("[←/→]", key_style),
+                        Span::styled(format!(": {}  ", tr!("hint.adjust")), white),
+                        Span::styled("[G]", key_style),
+                        Span::styled(format!(": {}  ", tr!("hint.suggest")), white),
+                        Span::styled("[R]", key_style),
+                        Span::styled(format!(": {}  ", tr!("hint.reset")), white),
+                        Span::styled("[Enter]", key_style),
+                        Span::styled(format!(": {}", tr!("hint.next")), white),
+                    ]));
+                }
+                lines
+            }
+            _ => vec![Spans::from(Span::styled(" ...", white))],
+        };
+
+        let handicap_block = Block::default()
+            .title(format!(" {} ", tr!("setup.handicaps")))
+            .title_alignment(Alignment::Left)
+            .borders(Borders::ALL);
+
+        f.render_widget(
+            Paragraph::new(handicap_text).block(handicap_block),
+            chunks[5],
+        );
+
+        let vpn_pos = match (&self.state, &self.board) {
+            (
+                AppState::PlayerInput(_)
+                | AppState::RosterInput
+                | AppState::ModeInput
+                | AppState::VenueInput,
+                _,
+            ) => vec![Span::styled("...", white)],
+            (AppState::HandicapInput, None) => {
+                vec![Span::styled(
+                    format!(" {}", tr!("setup.generate_vpn")),
+                    white,
+                )]
+            }
+            (state, Some(board)) => self.position_spans(
+                matches!(state, AppState::VPNPositions),
+                board,
+                &FEATURES[..2],
             ),
             _ => panic!("Should never happen"),
         };
 
         let vpn_block = Block::default()
-            .title(" VPN ")
+            .title(format!(" {} ", tr!("setup.vpn")))
             .title_alignment(Alignment::Left)
             .borders(Borders::ALL);
 
-        let vpn_positions =
-            Paragraph::new(Spans::from(vec![Span::from(" VPN positions: "), vpn_pos]))
-                .block(vpn_block);
+        // Teleport rules, changed once the positions are generated
+        let vpn_key = |key: &'static str| {
+            if let AppState::VPNPositions = self.state {
+                Span::styled(key, key_style)
+            } else {
+                Span::from("")
+            }
+        };
+        let rules = &self.game.rules;
+        let vpn_positions = Paragraph::new(vec![
+            Spans::from(
+                [
+                    vec![Span::from(format!(" {} ", tr!("setup.vpn_positions")))],
+                    vpn_pos,
+                ]
+                .concat(),
+            ),
+            Spans::from(vec![
+                Span::from(format!(" {} ", tr!("setup.vpn_uses"))),
+                Span::styled(rules.vpn_uses.to_string(), bold),
+                vpn_key("  [U]"),
+                Span::from(format!("    {} ", tr!("setup.vpn_cooldown"))),
+                Span::styled(rules.vpn_cooldown.to_string(), bold),
+                vpn_key("  [C]"),
+                Span::from(format!("    {} ", tr!("setup.vpn_relocate"))),
+                Span::styled(
+                    match rules.vpn_relocate {
+                        0 => tr!("setup.never").to_string(),
+                        uses => uses.to_string(),
+                    },
+                    bold,
+                ),
+                vpn_key("  [M]"),
+            ]),
+        ])
+        .block(vpn_block);
 
         f.render_widget(
-            vpn_positions.style(Style::default().fg(Color::Magenta)),
-            chunks[2],
+            vpn_positions.style(Style::default().fg(theme().accent)),
+            chunks[6],
         );
 
-        let capture_pos = match (&self.state, &self.vpn_positions, &self.capture_positions) {
-            (AppState::PlayerInput(_), _, _) => Span::styled("...", white),
-            (AppState::VPNPositions, _, _) => {
-                Span::styled("Press Enter to generate Capture postions", white)
-            }
-            (AppState::CapturePositions, _, Some((p1, p2, p3))) => Span::styled(
-                format!("{} {} {}", p1.to_string(), p2.to_string(), p3.to_string()),
-                Style::default().add_modifier(Modifier::BOLD),
+        let capture_pos = match &self.board {
+            Some(board) => self.position_spans(
+                matches!(self.state, AppState::CapturePositions),
+                board,
+                &FEATURES[2..],
             ),
-            _ => panic!("Should never happen"),
+            None => vec![Span::styled("...", white)],
         };
 
         let capture_block = Block::default()
-            .title(" Captures ")
+            .title(format!(" {} ", tr!("capture.title")))
             .title_alignment(Alignment::Left)
             .borders(Borders::ALL);
 
-        let capture_positions = Paragraph::new(Spans::from(vec![
-            Span::from(" Capture point positions: "),
-            capture_pos,
-        ]))
+        // Board constraints, changed while positions are shown
+        let board_key = |key: &'static str| {
+            if let AppState::VPNPositions | AppState::CapturePositions = self.state {
+                Span::styled(key, key_style)
+            } else {
+                Span::from("")
+            }
+        };
+        let constraints = &self.game.rules.board;
+        let capture_positions = Paragraph::new(vec![
+            Spans::from(
+                [
+                    vec![Span::from(format!(" {} ", tr!("setup.capture_positions")))],
+                    capture_pos,
+                ]
+                .concat(),
+            ),
+            Spans::from(vec![
+                Span::from(format!(" {} ", tr!("setup.spacing"))),
+                Span::styled(constraints.spacing.to_string(), bold),
+                board_key("  [D]"),
+                Span::from(format!("    {} ", tr!("setup.symmetric"))),
+                Span::styled(
+                    if constraints.symmetric {
+                        tr!("setup.on")
+                    } else {
+                        tr!("setup.off")
+                    },
+                    bold,
+                ),
+                board_key("  [S]"),
+                Span::from(format!("    {} ", tr!("setup.center_reach"))),
+                Span::styled(constraints.center_reach.to_string(), bold),
+                board_key("    [G] "),
+                if let AppState::VPNPositions | AppState::CapturePositions = self.state {
+                    Span::from(tr!("setup.new_board"))
+                } else {
+                    Span::from("")
+                },
+            ]),
+            Spans::from(match &self.board {
+                Some(_) if self.entering_code => vec![
+                    Span::from(format!(" {} ", tr!("setup.code"))),
+                    Span::styled(
+                        format!("{}_", self.input),
+                        bold.bg(theme().key).fg(theme().background),
+                    ),
+                ],
+                Some(board) => vec![
+                    Span::from(format!(" {} ", tr!("setup.code"))),
+                    Span::styled(board.code(), bold),
+                    board_key("    [K] "),
+                    board_key(tr!("hint.enter_code")),
+                    board_key("    [X] "),
+                    board_key(tr!("hint.export")),
+                ],
+                None => vec![],
+            }),
+        ])
         .block(capture_block);
 
         f.render_widget(
-            capture_positions.style(Style::default().fg(Color::Yellow)),
-            chunks[3],
+            capture_positions.style(Style::default().fg(theme().highlight)),
+            chunks[7],
         );
+
+        if let (AppState::PlayerInput(AiSide::For), None) = (&self.state, &self.game.team_names) {
+            f.render_widget(
+                Paragraph::new(Spans::from(vec![
+                    Span::styled("[T]", key_style),
+                    Span::styled(format!(": {}    ", tr!("hint.tournament")), white),
+                    Span::styled("[L]", key_style),
+                    Span::styled(format!(": {}    ", tr!("hint.leaderboard")), white),
+                    Span::styled("[H]", key_style),
+                    Span::styled(format!(": {}    ", tr!("hint.history")), white),
+                    Span::styled("[F2]", key_style),
+                    Span::styled(format!(": {}", tr!("hint.theme", tr!(theme().name))), white),
+                ]))
+                .alignment(Alignment::Center),
+                chunks[8],
+            );
+        }
+
+        // Going back to a previous step, and how to change positions
+        let hint = |key: &'static str, text: &str| {
+            vec![
+                Span::styled(key, key_style),
+                Span::styled(format!(": {}  ", text), white),
+            ]
+        };
+        let hints = match self.state {
+            AppState::VPNPositions | AppState::CapturePositions if self.entering_code => [
+                hint("[0-9][A-Z]", tr!("hint.type_code")),
+                hint("[Enter]", tr!("hint.confirm")),
+                hint("[Backspace]", tr!("hint.erase")),
+            ]
+            .concat(),
+            AppState::VPNPositions | AppState::CapturePositions if self.entering_position => [
+                hint("[A-K][1-7]", tr!("hint.type_position")),
+                hint("[Enter]", tr!("hint.confirm")),
+                hint("[Backspace]", tr!("hint.erase")),
+            ]
+            .concat(),
+            AppState::VPNPositions | AppState::CapturePositions => [
+                hint("[↑/↓]", tr!("hint.select_item")),
+                hint("[R]", tr!("hint.reroll")),
+                hint("[E]", tr!("hint.enter_position")),
+                hint("[Enter]", tr!("hint.next")),
+                hint("[Shift+Tab]", tr!("hint.back")),
+            ]
+            .concat(),
+            ref state if state.previous_step().is_some() => hint("[Shift+Tab]", tr!("hint.back")),
+            _ => vec![],
+        };
+        let error = match (&self.position_error, &self.sheet_status) {
+            (Some(err), _) => Span::styled(err.description(), Style::default().fg(theme().error)),
+            (None, Some(Ok(()))) => Span::styled(
+                tr!(
+                    "sheet.saved",
+                    format!("{}.{{{}}}", SHEET_FILE, SHEET_EXTENSIONS.join(","))
+                ),
+                Style::default().fg(theme().success),
+            ),
+            (None, Some(Err(err))) => {
+                Span::styled(tr!("sheet.error", err), Style::default().fg(theme().error))
+            }
+            (None, None) => Span::from(""),
+        };
+        if !hints.is_empty() {
+            f.render_widget(
+                Paragraph::new(vec![Spans::from(error), Spans::from(hints)])
+                    .alignment(Alignment::Center),
+                chunks[8],
+            );
+        }
     }
 }
@@ -1,17 +1,24 @@
 use tui::{
     backend::Backend,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
 };
 
-use crate::app::data::game::{AiSide, Game};
+use crate::app::{
+    data::game::{AiSide, Game},
+    i18n::tr,
+    theme::theme,
+};
 
 impl Game {
     pub fn draw_side<B: Backend>(&self, f: &mut tui::Frame<B>, rect: Rect, ai_side: AiSide) {
         let title_block = Block::default()
-            .title(format!(" {} ", ai_side.name()))
+            .title(match self.team_name(&ai_side) {
+                Some(team) => format!(" {} - {} ", ai_side.label(), team),
+                None => format!(" {} ", ai_side.label()),
+            })
             .title_alignment(match ai_side {
                 AiSide::For => Alignment::Left,
                 AiSide::Against => Alignment::Right,
@@ -33,9 +40,9 @@ impl Game {
             .margin(2)
             .split(rect);
 
-        let turn = Paragraph::new(ai_side.name())
+        let turn = Paragraph::new(ai_side.label())
             .style(if self.turn == ai_side {
-                Style::default().bg(ai_side.color()).fg(Color::Black)
+                Style::default().bg(ai_side.color()).fg(theme().background)
             } else {
                 Style::default().fg(ai_side.color())
             })
@@ -55,16 +62,24 @@ impl Game {
 
         let desc = Paragraph::new(vec![
             Spans::from(vec![
-                Span::from("Number of player: "),
+                Span::from(format!("{} ", tr!("setup.nb_players"))),
                 Span::styled(format!("{}", side.nb_players), style),
             ]),
             Spans::from(vec![
-                Span::from("Number of rounds: "),
+                Span::from(format!("{} ", tr!("side.nb_rounds"))),
                 Span::styled(format!("{}", side.nb_rounds), style),
             ]),
             Spans::from(vec![
-                Span::from("Points (progress): "),
-                Span::styled(format!("{}", side.progress()), style),
+                Span::from(format!("{} ", tr!("side.points"))),
+                Span::styled(
+                    // Hidden during Lucy's blackout
+                    if self.is_blackout() {
+                        "???".to_string()
+                    } else {
+                        side.progress().to_string()
+                    },
+                    style,
+                ),
             ]),
         ])
         .alignment(Alignment::Left);
@@ -0,0 +1,177 @@
+use std::mem;
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    symbols,
+    text::{Span, Spans},
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+};
+
+use crate::app::{
+    data::{
+        game::{AiSide, Game},
+        timeline::Moment,
+    },
+    i18n::tr,
+    theme::theme,
+};
+
+// Below this, the Play screen leaves the chart out
+pub const MIN_TIMELINE_HEIGHT: u16 = 6;
+
+impl Moment {
+    pub fn name(&self) -> &str {
+        match self {
+            Moment::Race(_) => tr!("timeline.races"),
+            Moment::Challenge(_) => tr!("timeline.challenges"),
+            Moment::Database(_) => tr!("timeline.databases"),
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            Moment::Race(_) => theme().key,
+            Moment::Challenge(_) => theme().highlight,
+            Moment::Database(_) => theme().success,
+        }
+    }
+}
+
+impl Game {
+    // Progress of both teams at every end of turn, with a dot where a race, challenge or database moved it
+    pub fn draw_timeline<B: Backend>(&self, f: &mut tui::Frame<B>, rect: Rect) {
+        let block = Block::default()
+            .title(format!(" {} ", tr!("timeline.title")))
+            .style(Style::default().fg(theme().highlight))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL);
+
+        if self.is_blackout() {
+            f.render_widget(
+                Paragraph::new("???")
+                    .style(Style::default().fg(theme().text))
+                    .alignment(Alignment::Center)
+                    .block(block),
+                rect,
+            );
+            return;
+        }
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+            .split(block.inner(rect));
+        f.render_widget(block, rect);
+
+        let lines: Vec<(AiSide, Vec<(f64, f64)>)> = [AiSide::For, AiSide::Against]
+            .into_iter()
+            .map(|ai_side| {
+                let points = self
+                    .timeline
+                    .iter()
+                    .enumerate()
+                    .map(|(turn, snapshot)| (turn as f64, snapshot.progress(&ai_side) as f64))
+                    .collect();
+                (ai_side, points)
+            })
+            .collect();
+
+        // Dots sit on the line of the team the moment helped, between both lines otherwise
+        let kinds = [
+            Moment::Race(None),
+            Moment::Challenge(None),
+            Moment::Database(AiSide::For),
+        ];
+        let dots: Vec<(&Moment, Vec<(f64, f64)>)> = kinds
+            .iter()
+            .map(|kind| {
+                let points = self
+                    .timeline
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(turn, snapshot)| {
+                        snapshot
+                            .moments
+                            .iter()
+                            .filter(|moment| mem::discriminant(*moment) == mem::discriminant(kind))
+                            .map(move |moment| {
+                                let progress = match moment.team() {
+                                    Some(ai_side) => snapshot.progress(ai_side) as f64,
+                                    None => (snapshot.for_ai + snapshot.against_ai) as f64 / 2.0,
+                                };
+                                (turn as f64, progress)
+                            })
+                    })
+                    .collect();
+                (kind, points)
+            })
+            .filter(|(_, points): &(&Moment, Vec<(f64, f64)>)| !points.is_empty())
+            .collect();
+
+        // Own legend line, tui's one would hide the end of the lines
+        let mut legend = vec![];
+        for (ai_side, _) in &lines {
+            legend.push(Span::styled(
+                format!("── {}  ", self.team_name(ai_side).unwrap_or(ai_side.name())),
+                Style::default().fg(ai_side.color()),
+            ));
+        }
+        for (kind, _) in &dots {
+            legend.push(Span::styled(
+                format!("• {}  ", kind.name()),
+                Style::default().fg(kind.color()),
+            ));
+        }
+        f.render_widget(
+            Paragraph::new(Spans::from(legend)).alignment(Alignment::Center),
+            chunks[1],
+        );
+
+        let mut datasets: Vec<Dataset> = lines
+            .iter()
+            .map(|(ai_side, points)| {
+                Dataset::default()
+                    .marker(symbols::Marker::Braille)
+                    .graph_type(GraphType::Line)
+                    .style(Style::default().fg(ai_side.color()))
+                    .data(points)
+            })
+            .collect();
+        datasets.extend(dots.iter().map(|(kind, points)| {
+            Dataset::default()
+                .marker(symbols::Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(kind.color()))
+                .data(points)
+        }));
+
+        let turns = self.timeline.len().saturating_sub(1).max(1);
+        let top = self
+            .timeline
+            .iter()
+            .map(|snapshot| snapshot.for_ai.max(snapshot.against_ai))
+            .max()
+            .unwrap_or(0)
+            .max(self.rules.mode.goal());
+        let label = |text: String| Span::styled(text, Style::default().fg(theme().text));
+
+        let chart = Chart::new(datasets)
+            .style(Style::default().fg(theme().highlight))
+            .x_axis(
+                Axis::default()
+                    .title(label(tr!("timeline.turns").to_string()))
+                    .style(Style::default().fg(theme().text))
+                    .bounds([0.0, turns as f64])
+                    .labels(vec![label("0".to_string()), label(turns.to_string())]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(theme().text))
+                    .bounds([0.0, top as f64])
+                    .labels(vec![label("0".to_string()), label(top.to_string())]),
+            )
+            .hidden_legend_constraints((Constraint::Length(0), Constraint::Length(0)));
+        f.render_widget(chart, chunks[0]);
+    }
+}
@@ -11,7 +11,7 @@ pub struct Title<'a> {
 }
 
 impl Title<'_> {
-    pub fn new(title: &str) -> Title {
+    pub fn new(title: &str) -> Title<'_> {
         Title { text: title }
     }
 }
@@ -0,0 +1,275 @@
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+};
+
+use crate::app::{
+    data::{
+        game::AiSide,
+        tournament::{Format, MAX_TEAMS, MIN_TEAMS},
+    },
+    i18n::tr,
+    theme::theme,
+    App,
+};
+
+impl Format {
+    pub fn name(&self) -> &str {
+        match self {
+            Format::RoundRobin => tr!("tournament.round_robin"),
+            Format::Bracket => tr!("tournament.bracket"),
+        }
+    }
+}
+
+impl App<'_> {
+    pub fn draw_registration<B: Backend>(&mut self, f: &mut tui::Frame<B>, rect: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .vertical_margin(8)
+            .horizontal_margin(60)
+            .constraints(
+                [
+                    Constraint::Length(3),                        // Format
+                    Constraint::Length(3),                        // Input
+                    Constraint::Length(MAX_TEAMS as u16 / 2 + 2), // Teams
+                    Constraint::Length(3),                        // Keys
+                    Constraint::Min(0),
+                ]
+                .as_ref(),
+            )
+            .split(rect);
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let key_style = bold.fg(theme().key);
+
+        let format = Paragraph::new(Spans::from(vec![
+            Span::from(format!(" {} ", tr!("tournament.format"))),
+            Span::styled(self.registration.format.name(), bold),
+        ]))
+        .block(
+            Block::default()
+                .title(format!(" {} ", tr!("tournament.title")))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent)),
+        );
+        f.render_widget(format, chunks[0]);
+
+        let input = Paragraph::new(Spans::from(vec![
+            Span::from(format!(" {} ", tr!("tournament.team_name"))),
+            Span::styled(format!("{}_", self.input), bold),
+        ]))
+        .block(
+            Block::default()
+                .title(format!(" {} ", tr!("tournament.new_team")))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().highlight)),
+        );
+        f.render_widget(input, chunks[1]);
+
+        let teams_block = Block::default()
+            .title(format!(
+                " {} ",
+                tr!("tournament.teams", self.registration.teams.len(), MAX_TEAMS)
+            ))
+            .borders(Borders::ALL);
+        f.render_widget(teams_block, chunks[2]);
+
+        let team_cols = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 2), Constraint::Ratio(1, 2)].as_ref())
+            .margin(1)
+            .split(chunks[2]);
+        let half = MAX_TEAMS / 2;
+        for (col, teams) in self.registration.teams.chunks(half).enumerate() {
+            let lines: Vec<Spans> = teams
+                .iter()
+                .enumerate()
+                .map(|(i, team)| Spans::from(format!(" {:>2}. {}", col * half + i + 1, team)))
+                .collect();
+            f.render_widget(Paragraph::new(lines), team_cols[col]);
+        }
+
+        let mut keys = vec![
+            Span::styled(" [Enter]", key_style),
+            Span::from(format!(": {}    ", tr!("hint.add_team"))),
+            Span::styled("[Backspace]", key_style),
+            Span::from(format!(": {}    ", tr!("hint.delete"))),
+            Span::styled("[Tab]", key_style),
+            Span::from(format!(": {}", tr!("hint.change_format"))),
+        ];
+        if self.registration.teams.len() < MIN_TEAMS {
+            keys.push(Span::styled(
+                format!("    {}", tr!("tournament.min_teams", MIN_TEAMS)),
+                Style::default().fg(theme().error),
+            ));
+        }
+        let mut lines = vec![Spans::from(keys)];
+        if let Some(err) = &self.tournament_error {
+            lines.push(Spans::from(Span::styled(
+                format!(" {}", err),
+                Style::default().fg(theme().error),
+            )));
+        }
+        f.render_widget(Paragraph::new(lines), chunks[3]);
+    }
+
+    pub fn draw_standings<B: Backend>(&mut self, f: &mut tui::Frame<B>, rect: Rect) {
+        let tournament = match self.tournament.as_mut() {
+            Some(tournament) => tournament,
+            None => return,
+        };
+
+        // Every team when the screen has room, else the table scrolls
+        let room = rect.height.saturating_sub(2 * 8 + 1 + 6);
+        let height = (tournament.teams.len() as u16 + 4).min(room);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .vertical_margin(8)
+            .horizontal_margin(50)
+            .constraints(
+                [
+                    Constraint::Length(height), // Standings
+                    Constraint::Length(1),
+                    Constraint::Length(6), // Next match and file errors
+                    Constraint::Min(0),
+                ]
+                .as_ref(),
+            )
+            .split(rect);
+
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let key_style = bold.fg(theme().key);
+
+        let standings = tournament.standings();
+        // Keep the last rows on screen when scrolling
+        let visible = chunks[0].height.saturating_sub(4) as usize;
+        let hidden = standings.len().saturating_sub(visible);
+        self.scroll = self.scroll.min(hidden as u16);
+
+        let rows: Vec<Row> = standings
+            .iter()
+            .enumerate()
+            .skip(self.scroll as usize)
+            .map(|(rank, s)| {
+                Row::new(vec![
+                    Cell::from(format!("{}.", rank + 1)),
+                    Cell::from(tournament.teams[s.team].clone()),
+                    Cell::from(s.played.to_string()),
+                    Cell::from(s.wins.to_string()),
+                    Cell::from(s.draws.to_string()),
+                    Cell::from(s.losses.to_string()),
+                    Cell::from(format!("{:+}", s.differential)),
+                    Cell::from(Span::styled(s.points().to_string(), bold)),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(rows)
+            .header(
+                Row::new(vec![
+                    "#",
+                    tr!("tournament.team"),
+                    tr!("tournament.played"),
+                    tr!("tournament.wins"),
+                    tr!("tournament.draws"),
+                    tr!("tournament.losses"),
+                    "+/-",
+                    tr!("tournament.points"),
+                ])
+                .style(bold.fg(theme().highlight))
+                .bottom_margin(1),
+            )
+            .block(
+                Block::default()
+                    .title(format!(
+                        " {} ",
+                        tr!("tournament.standings", tournament.format.name())
+                    ))
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().accent)),
+            )
+            .widths(&[
+                Constraint::Length(4),
+                Constraint::Min(20),
+                Constraint::Length(7),
+                Constraint::Length(4),
+                Constraint::Length(4),
+                Constraint::Length(4),
+                Constraint::Length(6),
+                Constraint::Length(4),
+            ]);
+        f.render_widget(table, chunks[0]);
+
+        let mut next = match tournament.next_match() {
+            Some(index) => {
+                let m = &tournament.matches[index];
+                vec![
+                    Spans::from(format!(" {}", tr!("tournament.round", m.round))),
+                    Spans::from(vec![
+                        Span::from(format!(" {} ", tr!("tournament.next_match"))),
+                        Span::styled(
+                            tournament.teams[m.home].clone(),
+                            bold.fg(AiSide::For.color()),
+                        ),
+                        Span::from(format!(
+                            " ({}) {} ",
+                            AiSide::For.name(),
+                            tr!("tournament.versus")
+                        )),
+                        Span::styled(
+                            tournament.teams[m.away].clone(),
+                            bold.fg(AiSide::Against.color()),
+                        ),
+                        Span::from(format!(" ({})", AiSide::Against.name())),
+                    ]),
+                ]
+            }
+            None => vec![
+                Spans::from(""),
+                Spans::from(vec![
+                    Span::from(format!(" {} ", tr!("tournament.champion"))),
+                    Span::styled(
+                        tournament
+                            .champion()
+                            .map(|team| tournament.teams[team].clone())
+                            .unwrap_or_default(),
+                        bold.fg(theme().success),
+                    ),
+                    Span::styled(" \\o/", Style::default().fg(theme().success)),
+                ]),
+            ],
+        };
+
+        let mut keys = vec![Span::from(" ")];
+        if hidden > 0 {
+            keys.push(Span::styled("[↑/↓]", key_style));
+            keys.push(Span::from(format!(": {}    ", tr!("hint.scroll"))));
+        }
+        if tournament.next_match().is_some() {
+            keys.push(Span::styled("[Enter]", key_style));
+            keys.push(Span::from(format!(": {}    ", tr!("hint.play_next"))));
+        }
+        keys.extend([
+            Span::styled("[N]", key_style),
+            Span::from(format!(": {}    ", tr!("hint.new_tournament"))),
+            Span::styled("[ESC]", key_style),
+            Span::from(format!(": {}", tr!("hint.quit_saved"))),
+        ]);
+        next.push(Spans::from(keys));
+        if let Some(err) = &self.tournament_error {
+            next.push(Spans::from(Span::styled(
+                format!(" {}", err),
+                Style::default().fg(theme().error),
+            )));
+        }
+        f.render_widget(
+            Paragraph::new(next).block(Block::default().borders(Borders::ALL)),
+            chunks[2],
+        );
+    }
+}
@@ -0,0 +1,83 @@
+use crossterm::event::KeyCode;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::Span,
+    widgets::Paragraph,
+};
+
+use crate::app::{
+    data::{
+        game::{AiSide, Game},
+        vpn::VpnStatus,
+    },
+    i18n::tr,
+    mouse::Targets,
+    theme::theme,
+};
+
+// Teleport keys, (For AI, Against AI)
+pub const VPN_KEYS: (char, char) = ('p', 'g');
+
+impl Game {
+    // State of each team's VPN on its own line, clicking a line logs a teleport
+    pub fn draw_vpns<B: Backend>(&self, f: &mut tui::Frame<B>, rect: Rect, targets: &mut Targets) {
+        let lines = [(AiSide::For, VPN_KEYS.0), (AiSide::Against, VPN_KEYS.1)];
+        for (row, (ai_side, key)) in lines.into_iter().enumerate() {
+            if row as u16 >= rect.height {
+                break;
+            }
+            let line = Rect {
+                y: rect.y + row as u16,
+                height: 1,
+                ..rect
+            };
+            let vpn = match self.vpn(&ai_side) {
+                Some(vpn) => vpn,
+                None => continue,
+            };
+            let (status, color) = match vpn.status(self.rules.vpn_uses) {
+                VpnStatus::Ready(left) => (tr!("vpn.ready", left), theme().success),
+                VpnStatus::Spent => (tr!("vpn.spent").to_string(), theme().text),
+                VpnStatus::Cooldown(rounds) => (tr!("vpn.cooldown", rounds), theme().error),
+            };
+            let uses: Vec<_> = self
+                .vpn_log
+                .iter()
+                .filter(|logged| logged.ai_side == ai_side)
+                .collect();
+            let mut details = tr!("vpn.uses", uses.len());
+            if let Some(last) = uses.last() {
+                details += &tr!("vpn.last", last.from, last.round);
+            }
+            if self.rules.vpn_relocate > 0 {
+                details += &tr!("vpn.moves_in", self.rules.vpn_relocate - vpn.uses);
+            }
+            let hint = targets.hint_line(
+                line,
+                vec![(
+                    vec![
+                        Span::styled(
+                            format!("[{}]", key.to_ascii_uppercase()),
+                            Style::default()
+                                .add_modifier(Modifier::BOLD)
+                                .fg(theme().key),
+                        ),
+                        Span::styled(
+                            format!(" {} ", tr!("vpn.label", ai_side.label(), vpn.position)),
+                            Style::default().fg(ai_side.color()),
+                        ),
+                        Span::styled(
+                            status,
+                            Style::default().add_modifier(Modifier::BOLD).fg(color),
+                        ),
+                        Span::styled(format!(" ({})", details), Style::default().fg(theme().text)),
+                    ],
+                    Some(KeyCode::Char(key)),
+                )],
+            );
+            f.render_widget(Paragraph::new(hint).alignment(Alignment::Center), line);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use std::{fs, sync::OnceLock};
+
+pub const CONFIG_FILE: &str = "cyberconnect.conf";
+
+// `key=value` pairs given on the command line, they win over the file
+static OVERRIDES: OnceLock<Vec<(String, String)>> = OnceLock::new();
+
+pub fn set_overrides(overrides: Vec<(String, String)>) {
+    let _ = OVERRIDES.set(overrides);
+}
+
+// Reads a `key = value` line from the config file, `#` starts a comment
+pub fn value(key: &str) -> Option<String> {
+    let overridden = OVERRIDES
+        .get()
+        .and_then(|overrides| overrides.iter().rev().find(|(k, _)| k == key));
+    if let Some((_, value)) = overridden {
+        return Some(value.clone());
+    }
+    let content = fs::read_to_string(CONFIG_FILE).ok()?;
+    content
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .filter_map(|line| line.split_once('='))
+        .find(|(k, _)| k.trim() == key)
+        .map(|(_, v)| v.trim().to_string())
+}
@@ -15,7 +15,7 @@ use tui::{
     Terminal,
 };
 
-pub fn run(tick_rate: Duration) -> Result<(), Box<dyn Error>> {
+pub fn run(app: App, tick_rate: Duration) -> Result<(), Box<dyn Error>> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -24,8 +24,7 @@ pub fn run(tick_rate: Duration) -> Result<(), Box<dyn Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app and run it
-    let app = App::new();
+    // Run the app
     let res = run_app(&mut terminal, app, tick_rate);
 
     // Restore terminal
@@ -59,8 +58,10 @@ fn run_app<B: Backend>(
             .unwrap_or_else(|| Duration::from_secs(0));
 
         if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                app.on_key(key.code);
+            match event::read()? {
+                Event::Key(key) => app.on_key(key.code),
+                Event::Mouse(mouse) => app.on_mouse(mouse),
+                _ => {}
             }
         }
 
@@ -1,13 +1,46 @@
 mod app;
+mod config;
 mod crossterm;
 
-use std::{error::Error, time::Duration};
+use std::{env, error::Error, process};
 
-use crate::crossterm::run;
+use crate::{
+    app::{
+        cli::{self, Command, USAGE},
+        history, i18n,
+        simulate::simulate,
+        theme, App,
+    },
+    crossterm::run,
+};
 
-const TICK_RATE: Duration = Duration::from_millis(100);
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let cli = match cli::parse(&args) {
+        Ok(cli) => cli,
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+    config::set_overrides(cli.overrides);
+    i18n::init(cli.lang.as_deref());
+    theme::init(cli.theme.as_deref());
 
-fn main() -> Result<(), Box<dyn Error>> {
-    run(TICK_RATE)?;
-    Ok(())
+    let res: Result<(), Box<dyn Error>> = match cli.command {
+        Command::Play { setup, tick_rate } => {
+            App::with_setup(&setup).map_or_else(|err| Err(err.into()), |app| run(app, tick_rate))
+        }
+        Command::Simulate { setup, games } => simulate(&setup, games),
+        Command::Replay { id, delay } => history::replay(id, delay),
+        Command::History(args) => history::command(&args),
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+    };
+    if let Err(err) = res {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
 }